@@ -1,9 +1,50 @@
+use std::fmt::Debug;
+
 use num::traits::Pow;
+use rand::Rng;
 
 use crate::{
-    elliptic_curve::point_elliptic_curve::PointEllipticCurve, finite_body::finite_body::FiniteBody,
+    big_integer::BigInteger,
+    elliptic_curve::{
+        curve::Curve,
+        error::EllipticCurveError,
+        fixed_base::FixedBaseTable,
+        point_elliptic_curve::PointEllipticCurve,
+        rfc7748::{x25519, x448, X25519_BASE_POINT, X25519_SIZE, X448_BASE_POINT, X448_SIZE},
+        scalar::Scalar,
+    },
+    finite_body::finite_body::FiniteBody,
 };
 
+/// El intercambio de Diffie-Hellman sobre una curva: cada parte elige una clave privada, publica la
+/// clave publica que le corresponde y combina su clave privada con la publica del otro. Lo
+/// implementan `Agreement` (curvas de Weierstrass sobre F_p chicos) y `X25519` / `X448` (RFC 7748),
+/// asi el mismo flujo corre sobre cualquiera de ellos.
+pub trait DiffieHellman {
+    type PrivateKey: Copy;
+    type PublicKey: Copy;
+    type SharedSecret: PartialEq + Debug;
+
+    fn random_private_key<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::PrivateKey;
+
+    fn public_key(&self, private_key: Self::PrivateKey) -> Self::PublicKey;
+
+    /// Falla si la clave publica no es valida o si el secreto es trivial.
+    fn shared_secret(
+        &self,
+        public_key: Self::PublicKey,
+        private_key: Self::PrivateKey,
+    ) -> Result<Self::SharedSecret, EllipticCurveError>;
+}
+
+/// X25519 (RFC 7748): claves de 32 bytes y la coordenada u de Curve25519 como clave publica.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct X25519;
+
+/// X448 (RFC 7748): claves de 56 bytes y la coordenada u de Curve448 como clave publica.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct X448;
+
 pub struct Agreement {
     pub p: i32,
     pub a: FiniteBody<i32>,
@@ -11,49 +52,59 @@ pub struct Agreement {
     pub g: PointEllipticCurve<FiniteBody<i32>>,
     pub order_g: usize,
     pub cuantity_points_curve: usize,
+    pub fixed_base_table: Option<FixedBaseTable<FiniteBody<i32>>>,
 }
 
 impl Agreement {
+    /// Falla si la curva es singular o si el generador no pertenece a la curva (a, b).
     pub fn new(
         p: i32,
         a: FiniteBody<i32>,
         b: FiniteBody<i32>,
         g: PointEllipticCurve<FiniteBody<i32>>,
-    ) -> Self {
-        let mut order_g = 0;
-
-        // Brute-force search
-        for k in 1..p {
-            let k = k as usize;
-            let res = g * k;
-            if res == PointEllipticCurve::new_inf(a, b) {
-                order_g = k; // se incluye el punto del infinito en la cuenta. Es decir {G, 2G, ..., (n-1)G, nG} donde nG sera el punto del infinito
-                break;
-            }
+    ) -> Result<Self, EllipticCurveError> {
+        let curve = Curve::new(a, b)?;
+        if !curve.contains(&g) {
+            return Err(EllipticCurveError::PointNotOnCurve);
         }
 
-        let mut cuantity_points_curve = 0;
-
-        // Brute-force search
-        for x in 0..p {
-            let x_val = FiniteBody::new(p, x);
-            let y_squared = x_val.pow(3) + a * x_val + b;
-            for y in 0..p {
-                let y_val = FiniteBody::new(p, y);
-                if y_squared == y_val.pow(2) {
-                    cuantity_points_curve += 1;
-                }
-            }
-        }
+        // #E se calcula una sola vez: da el orden de G y la cantidad de puntos afines
+        let group_order = curve.group_order();
+        let order_g = usize::try_from(g.order_with_group_order(group_order))
+            .expect("the order of G divides #E");
+        let cuantity_points_curve =
+            usize::try_from(group_order).expect("#E is close to p, which is an i32") - 1;
 
-        Self {
+        Ok(Self {
             p,
             a,
             b,
             g,
             order_g,
             cuantity_points_curve,
+            fixed_base_table: None,
+        })
+    }
+
+    /// Precalcula una tabla de base fija para G con ventana `window`, asi `generate_public_key` hace
+    /// una suma por ventana en lugar de recorrer la escalera de Montgomery.
+    pub fn with_fixed_base_table(mut self, window: usize) -> Self {
+        self.fixed_base_table = Some(FixedBaseTable::new(&self.g, self.key_space(), window));
+        self
+    }
+
+    /// Usa una tabla ya calculada (por ejemplo cargada con `FixedBaseTable::from_bytes`). Falla si
+    /// la tabla no es para el generador G de este acuerdo o si su orden no es el de G (las claves se
+    /// reducirian modulo otro numero).
+    pub fn set_fixed_base_table(
+        &mut self,
+        table: FixedBaseTable<FiniteBody<i32>>,
+    ) -> Result<(), EllipticCurveError> {
+        if table.base() != self.g || table.order() != self.key_space() {
+            return Err(EllipticCurveError::InvalidFixedBaseTable);
         }
+        self.fixed_base_table = Some(table);
+        Ok(())
     }
 
     pub fn order_g(&self) -> usize {
@@ -64,16 +115,207 @@ impl Agreement {
         self.cuantity_points_curve + 1 // sumando el punto del infinito
     }
 
-    pub fn generate_public_key(&self, private_key: usize) -> PointEllipticCurve<FiniteBody<i32>> {
-        self.g * private_key
+    /// Clave privada uniforme en [1, n - 1], con n el orden de G.
+    pub fn random_private_key<R: Rng + ?Sized>(&self, rng: &mut R) -> Scalar {
+        Scalar::random_nonzero(self.key_space(), rng)
+    }
+
+    /// k * G con la tabla de base fija si hay una, o con la escalera de Montgomery. Los dos metodos
+    /// hacen la misma cantidad de operaciones de grupo para cualquier clave privada (no son de tiempo
+    /// constante, ver `PointEllipticCurve::mul_ladder`).
+    pub fn generate_public_key<K: Into<BigInteger>>(
+        &self,
+        private_key: K,
+    ) -> PointEllipticCurve<FiniteBody<i32>> {
+        match &self.fixed_base_table {
+            Some(table) => table.mul(private_key),
+            None => self.secret_multiple(self.g, private_key),
+        }
+    }
+
+    /// Calcula el secreto compartido, validando antes la clave publica recibida. Si el cofactor es 1
+    /// (G genera toda la curva) alcanza con la validacion parcial, si no se hace la validacion
+    /// completa.
+    pub fn generate_shared_secret<K: Into<BigInteger>>(
+        &self,
+        public_key: PointEllipticCurve<FiniteBody<i32>>,
+        private_key: K,
+    ) -> Result<PointEllipticCurve<FiniteBody<i32>>, EllipticCurveError> {
+        if self.quantity_points_curve() == self.order_g() {
+            self.validate_public_key_partial(&public_key)?;
+        } else {
+            self.validate_public_key(&public_key)?;
+        }
+
+        // ya validada, n * Q = O
+        let shared_secret = self.secret_multiple(public_key, private_key);
+        if shared_secret.is_identity() {
+            return Err(EllipticCurveError::PointAtInfinity);
+        }
+        Ok(shared_secret)
     }
 
-    pub fn generate_shared_secret(
+    /// Secreto compartido sin validar la clave publica. Con un punto fuera de la curva o de orden chico
+    /// el resultado filtra informacion de la clave privada ("invalid curve" / "small subgroup" attacks).
+    pub fn generate_shared_secret_unchecked<K: Into<BigInteger>>(
         &self,
         public_key: PointEllipticCurve<FiniteBody<i32>>,
-        private_key: usize,
+        private_key: K,
     ) -> PointEllipticCurve<FiniteBody<i32>> {
-        public_key * private_key
+        // Q puede no tener orden n, asi que no se reduce la clave: se recorren todos sus bits
+        let private_key = private_key.into();
+        let bits = private_key.bits().max(usize::BITS as usize);
+        public_key.mul_ladder(private_key, bits)
+    }
+
+    // k * P con la escalera de Montgomery, para P con n * P = O.
+    fn secret_multiple<K: Into<BigInteger>>(
+        &self,
+        point: PointEllipticCurve<FiniteBody<i32>>,
+        private_key: K,
+    ) -> PointEllipticCurve<FiniteBody<i32>> {
+        point.mul_ladder_mod(private_key, self.order_g())
+    }
+
+    // Las claves privadas son los enteros modulo el orden de G
+    fn key_space(&self) -> BigInteger {
+        BigInteger::from(self.order_g())
+    }
+
+    /// Clave publica recibida en formato SEC1 (comprimida o no, ver `PointEllipticCurve::to_sec1`),
+    /// decodificada y validada con `validate_public_key`.
+    pub fn public_key_from_sec1(
+        &self,
+        bytes: &[u8],
+    ) -> Result<PointEllipticCurve<FiniteBody<i32>>, EllipticCurveError> {
+        let curve = Curve::new(self.a, self.b)?;
+        let public_key = PointEllipticCurve::from_sec1(bytes, &curve)?;
+        self.validate_public_key(&public_key)?;
+        Ok(public_key)
+    }
+
+    /// Validacion completa de una clave publica (NIST SP 800-56A, 5.6.2.3.3): Q no es el punto del
+    /// infinito, sus coordenadas son elementos de F_p, Q esta en la curva y n * Q = O.
+    pub fn validate_public_key(
+        &self,
+        public_key: &PointEllipticCurve<FiniteBody<i32>>,
+    ) -> Result<(), EllipticCurveError> {
+        self.validate_public_key_partial(public_key)?;
+
+        if *public_key * self.order_g() != PointEllipticCurve::new_inf(self.a, self.b) {
+            return Err(EllipticCurveError::PointNotInSubgroup);
+        }
+        Ok(())
+    }
+
+    /// Validacion parcial (NIST SP 800-56A, 5.6.2.3.4): igual que la completa pero sin calcular n * Q.
+    /// Solo es suficiente si el orden de la curva es primo (cofactor 1), porque ahi todo punto
+    /// distinto de O de la curva genera el mismo grupo que G.
+    pub fn validate_public_key_partial(
+        &self,
+        public_key: &PointEllipticCurve<FiniteBody<i32>>,
+    ) -> Result<(), EllipticCurveError> {
+        let (x, y) = match public_key.coordinates() {
+            Some((x, y)) => (x, y),
+            None => return Err(EllipticCurveError::PointAtInfinity),
+        };
+
+        let in_range = |coordinate: FiniteBody<i32>| {
+            coordinate.p == self.p && coordinate.value >= 0 && coordinate.value < self.p
+        };
+        if !in_range(x) || !in_range(y) {
+            return Err(EllipticCurveError::CoordinateOutOfRange);
+        }
+
+        if public_key.a != self.a
+            || public_key.b != self.b
+            || y.pow(2) != x.pow(3) + self.a * x + self.b
+        {
+            return Err(EllipticCurveError::PointNotOnCurve);
+        }
+        Ok(())
+    }
+}
+
+impl DiffieHellman for Agreement {
+    type PrivateKey = Scalar;
+    type PublicKey = PointEllipticCurve<FiniteBody<i32>>;
+    type SharedSecret = PointEllipticCurve<FiniteBody<i32>>;
+
+    fn random_private_key<R: Rng + ?Sized>(&self, rng: &mut R) -> Scalar {
+        Agreement::random_private_key(self, rng)
+    }
+
+    fn public_key(&self, private_key: Scalar) -> Self::PublicKey {
+        self.generate_public_key(private_key)
+    }
+
+    fn shared_secret(
+        &self,
+        public_key: Self::PublicKey,
+        private_key: Scalar,
+    ) -> Result<Self::SharedSecret, EllipticCurveError> {
+        self.generate_shared_secret(public_key, private_key)
+    }
+}
+
+impl DiffieHellman for X25519 {
+    type PrivateKey = [u8; X25519_SIZE];
+    type PublicKey = [u8; X25519_SIZE];
+    type SharedSecret = [u8; X25519_SIZE];
+
+    /// 32 bytes uniformes, el ajuste de los bits lo hace `x25519`.
+    fn random_private_key<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::PrivateKey {
+        let mut private_key = [0; X25519_SIZE];
+        rng.fill_bytes(&mut private_key);
+        private_key
+    }
+
+    fn public_key(&self, private_key: Self::PrivateKey) -> Self::PublicKey {
+        x25519(private_key, X25519_BASE_POINT)
+    }
+
+    /// Cualquier cadena de 32 bytes es una clave publica valida. Un punto de orden chico da un
+    /// secreto de todos ceros, que se rechaza (RFC 7748, 6.1).
+    fn shared_secret(
+        &self,
+        public_key: Self::PublicKey,
+        private_key: Self::PrivateKey,
+    ) -> Result<Self::SharedSecret, EllipticCurveError> {
+        non_zero(x25519(private_key, public_key))
+    }
+}
+
+impl DiffieHellman for X448 {
+    type PrivateKey = [u8; X448_SIZE];
+    type PublicKey = [u8; X448_SIZE];
+    type SharedSecret = [u8; X448_SIZE];
+
+    fn random_private_key<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::PrivateKey {
+        let mut private_key = [0; X448_SIZE];
+        rng.fill_bytes(&mut private_key);
+        private_key
+    }
+
+    fn public_key(&self, private_key: Self::PrivateKey) -> Self::PublicKey {
+        x448(private_key, X448_BASE_POINT)
+    }
+
+    /// Como en X25519, se rechaza el secreto de todos ceros (RFC 7748, 6.2).
+    fn shared_secret(
+        &self,
+        public_key: Self::PublicKey,
+        private_key: Self::PrivateKey,
+    ) -> Result<Self::SharedSecret, EllipticCurveError> {
+        non_zero(x448(private_key, public_key))
+    }
+}
+
+// u = 0 es el resultado de multiplicar un punto de orden chico por un multiplo del cofactor
+fn non_zero<const N: usize>(shared_secret: [u8; N]) -> Result<[u8; N], EllipticCurveError> {
+    match shared_secret.iter().all(|byte| *byte == 0) {
+        true => Err(EllipticCurveError::PointAtInfinity),
+        false => Ok(shared_secret),
     }
 }
 
@@ -81,6 +323,8 @@ impl Agreement {
 mod tests {
     use rand::Rng;
 
+    use crate::elliptic_curve::point_elliptic_curve::Coordinates;
+
     use super::*;
 
     #[test]
@@ -92,7 +336,7 @@ mod tests {
         let g1 =
             PointEllipticCurve::new(FiniteBody::new(p, 13), FiniteBody::new(p, 15), a, b).unwrap();
 
-        let agreement_g1 = Agreement::new(p, a, b, g1);
+        let agreement_g1 = Agreement::new(p, a, b, g1).unwrap();
         println!("Orden de [G1]: {:?}", agreement_g1.order_g());
         println!(
             "Cantidad de puntos de la curva: {:?}",
@@ -110,22 +354,24 @@ mod tests {
         let public_key_bob = agreement_g1.generate_public_key(private_key_bob);
 
         // ALICE recibe la clave publica de BOB
-        let shared_secret_alice =
-            agreement_g1.generate_shared_secret(public_key_bob, private_key_alice);
+        let shared_secret_alice = agreement_g1
+            .generate_shared_secret(public_key_bob, private_key_alice)
+            .unwrap();
 
         // BOB recibe la clave publica de ALICE
-        let shared_secret_bob =
-            agreement_g1.generate_shared_secret(public_key_alice, private_key_bob);
+        let shared_secret_bob = agreement_g1
+            .generate_shared_secret(public_key_alice, private_key_bob)
+            .unwrap();
 
         println!(
             "[G1] Clave compartida secreta que tendrá ALICE: (x: {:?}, y: {:?})",
-            shared_secret_alice.x.unwrap().as_value(),
-            shared_secret_alice.y.unwrap().as_value()
+            shared_secret_alice.x().unwrap().as_value(),
+            shared_secret_alice.y().unwrap().as_value()
         );
         println!(
             "[G1] Clave compartida secreta que tendrá BOB: (x: {:?}, y: {:?})",
-            shared_secret_bob.x.unwrap().as_value(),
-            shared_secret_bob.y.unwrap().as_value()
+            shared_secret_bob.x().unwrap().as_value(),
+            shared_secret_bob.y().unwrap().as_value()
         );
         assert_eq!(shared_secret_alice, shared_secret_bob);
     }
@@ -139,7 +385,7 @@ mod tests {
         let g2 =
             PointEllipticCurve::new(FiniteBody::new(p, 9), FiniteBody::new(p, 2), a, b).unwrap();
 
-        let agreement_g2 = Agreement::new(p, a, b, g2);
+        let agreement_g2 = Agreement::new(p, a, b, g2).unwrap();
         println!("Orden de [G2]: {:?}", agreement_g2.order_g());
         println!(
             "Cantidad de puntos de la curva: {:?}",
@@ -155,23 +401,368 @@ mod tests {
         let public_key_bob = agreement_g2.generate_public_key(private_key_bob);
 
         // ALICE recibe la clave publica de BOB
-        let shared_secret_alice =
-            agreement_g2.generate_shared_secret(public_key_bob, private_key_alice);
+        let shared_secret_alice = agreement_g2
+            .generate_shared_secret(public_key_bob, private_key_alice)
+            .unwrap();
 
         // BOB recibe la clave publica de ALICE
-        let shared_secret_bob =
-            agreement_g2.generate_shared_secret(public_key_alice, private_key_bob);
+        let shared_secret_bob = agreement_g2
+            .generate_shared_secret(public_key_alice, private_key_bob)
+            .unwrap();
 
         println!(
             "[G2] Clave compartida secreta que tendrá ALICE: (x: {:?}, y: {:?})",
-            shared_secret_alice.x.unwrap().as_value(),
-            shared_secret_alice.y.unwrap().as_value()
+            shared_secret_alice.x().unwrap().as_value(),
+            shared_secret_alice.y().unwrap().as_value()
         );
         println!(
             "[G2] Clave compartida secreta que tendrá BOB: (x: {:?}, y: {:?})",
-            shared_secret_bob.x.unwrap().as_value(),
-            shared_secret_bob.y.unwrap().as_value()
+            shared_secret_bob.x().unwrap().as_value(),
+            shared_secret_bob.y().unwrap().as_value()
         );
         assert_eq!(shared_secret_alice, shared_secret_bob);
     }
+
+    #[test]
+    fn test_03_singular_curve_is_rejected() {
+        let p = 43;
+        let a: FiniteBody<i32> = FiniteBody::new(p, 0);
+        let b: FiniteBody<i32> = FiniteBody::new(p, 6);
+        let g1 =
+            PointEllipticCurve::new(FiniteBody::new(p, 13), FiniteBody::new(p, 15), a, b).unwrap();
+
+        let zero = FiniteBody::new(p, 0);
+        assert_eq!(
+            Agreement::new(p, zero, zero, g1).err(),
+            Some(EllipticCurveError::SingularCurve)
+        );
+        assert_eq!(
+            Agreement::new(p, a, FiniteBody::new(p, 7), g1).err(),
+            Some(EllipticCurveError::PointNotOnCurve)
+        );
+    }
+
+    fn agreement_g1() -> Agreement {
+        let p = 43;
+        let a: FiniteBody<i32> = FiniteBody::new(p, 0);
+        let b: FiniteBody<i32> = FiniteBody::new(p, 6);
+        let g1 =
+            PointEllipticCurve::new(FiniteBody::new(p, 13), FiniteBody::new(p, 15), a, b).unwrap();
+        Agreement::new(p, a, b, g1).unwrap()
+    }
+
+    #[test]
+    fn test_04_validate_public_key() {
+        let agreement = agreement_g1();
+        for private_key in 1..agreement.order_g() {
+            let public_key = agreement.generate_public_key(private_key);
+            assert_eq!(agreement.validate_public_key(&public_key), Ok(()));
+            assert_eq!(agreement.validate_public_key_partial(&public_key), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_05_off_curve_point_is_rejected() {
+        let agreement = agreement_g1();
+        let p = agreement.p;
+        let off_curve = PointEllipticCurve {
+            coordinates: Coordinates::Affine {
+                x: FiniteBody::new(p, 13),
+                y: FiniteBody::new(p, 16),
+            },
+            a: agreement.a,
+            b: agreement.b,
+        };
+        assert_eq!(
+            agreement.validate_public_key(&off_curve),
+            Err(EllipticCurveError::PointNotOnCurve)
+        );
+        assert_eq!(
+            agreement.generate_shared_secret(off_curve, 5).err(),
+            Some(EllipticCurveError::PointNotOnCurve)
+        );
+
+        // punto valido de otra curva (b = 7): "invalid curve attack"
+        let other_curve = PointEllipticCurve::new(
+            FiniteBody::new(p, 2),
+            FiniteBody::new(p, 12),
+            agreement.a,
+            FiniteBody::new(p, 7),
+        )
+        .unwrap();
+        assert_eq!(
+            agreement.validate_public_key(&other_curve),
+            Err(EllipticCurveError::PointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn test_06_coordinates_out_of_range_are_rejected() {
+        let agreement = agreement_g1();
+        let p = agreement.p;
+        let g = agreement.g;
+
+        // (13 + 43, 15) cumple la ecuacion modulo 43 pero no es un elemento del cuerpo
+        let (_, y) = g.coordinates().unwrap();
+        let not_reduced = PointEllipticCurve {
+            coordinates: Coordinates::Affine {
+                x: FiniteBody { p, value: 13 + p },
+                y,
+            },
+            ..g
+        };
+        assert_eq!(
+            agreement.validate_public_key(&not_reduced),
+            Err(EllipticCurveError::CoordinateOutOfRange)
+        );
+
+        let other_field = PointEllipticCurve {
+            coordinates: Coordinates::Affine {
+                x: FiniteBody { p: 47, value: 13 },
+                y,
+            },
+            ..g
+        };
+        assert_eq!(
+            agreement.validate_public_key(&other_field),
+            Err(EllipticCurveError::CoordinateOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_07_identity_is_rejected() {
+        let agreement = agreement_g1();
+        let identity = PointEllipticCurve::new_inf(agreement.a, agreement.b);
+        assert_eq!(
+            agreement.validate_public_key(&identity),
+            Err(EllipticCurveError::PointAtInfinity)
+        );
+        assert_eq!(
+            agreement.generate_shared_secret(identity, 5).err(),
+            Some(EllipticCurveError::PointAtInfinity)
+        );
+    }
+
+    #[test]
+    fn test_08_small_order_point_is_rejected() {
+        let agreement = agreement_g1();
+        let p = agreement.p;
+
+        // #E = 39 = 3 * 13: 13 * G2 tiene orden 3 y no esta en el subgrupo de G1
+        let g2 = PointEllipticCurve::new(
+            FiniteBody::new(p, 9),
+            FiniteBody::new(p, 2),
+            agreement.a,
+            agreement.b,
+        )
+        .unwrap();
+        let small_order = g2 * 13;
+        assert_eq!(
+            small_order * 3,
+            PointEllipticCurve::new_inf(agreement.a, agreement.b)
+        );
+
+        // la validacion parcial no lo detecta, por eso el cofactor != 1 obliga a la completa
+        assert_eq!(agreement.validate_public_key_partial(&small_order), Ok(()));
+        assert_eq!(
+            agreement.validate_public_key(&small_order),
+            Err(EllipticCurveError::PointNotInSubgroup)
+        );
+        assert_eq!(
+            agreement.generate_shared_secret(small_order, 5).err(),
+            Some(EllipticCurveError::PointNotInSubgroup)
+        );
+
+        // sin validar, el secreto solo puede tomar 3 valores: revela la clave privada modulo 3
+        let leaked = agreement.generate_shared_secret_unchecked(small_order, 5);
+        assert_eq!(leaked, small_order * 2);
+    }
+
+    #[test]
+    fn test_09_keys_use_the_ladder() {
+        let agreement = agreement_g1();
+        let g2 = PointEllipticCurve::new(
+            FiniteBody::new(43, 9),
+            FiniteBody::new(43, 2),
+            agreement.a,
+            agreement.b,
+        )
+        .unwrap();
+        for private_key in 0..3 * agreement.order_g() {
+            assert_eq!(
+                agreement.generate_public_key(private_key),
+                agreement.g * private_key
+            );
+            assert_eq!(
+                agreement.generate_shared_secret_unchecked(g2, private_key),
+                g2 * private_key
+            );
+        }
+    }
+
+    #[test]
+    fn test_10_fixed_base_table() {
+        let agreement = agreement_g1().with_fixed_base_table(3);
+        let without_table = agreement_g1();
+        for private_key in 0..40 {
+            assert_eq!(
+                agreement.generate_public_key(private_key),
+                without_table.generate_public_key(private_key)
+            );
+        }
+
+        // tabla serializada y cargada en otro acuerdo
+        let bytes = agreement.fixed_base_table.as_ref().unwrap().to_bytes();
+        let mut loaded = agreement_g1();
+        loaded
+            .set_fixed_base_table(FixedBaseTable::from_bytes(&bytes).unwrap())
+            .unwrap();
+        assert_eq!(loaded.generate_public_key(7), agreement.g * 7_usize);
+
+        // una tabla para otro punto se rechaza
+        let g2 = PointEllipticCurve::new(
+            FiniteBody::new(43, 9),
+            FiniteBody::new(43, 2),
+            agreement.a,
+            agreement.b,
+        )
+        .unwrap();
+        assert_eq!(
+            loaded.set_fixed_base_table(FixedBaseTable::new(&g2, 39, 3)),
+            Err(EllipticCurveError::InvalidFixedBaseTable)
+        );
+        // y una para G con otro orden tambien
+        assert_eq!(
+            loaded.set_fixed_base_table(FixedBaseTable::new(&agreement.g, 39, 3)),
+            Err(EllipticCurveError::InvalidFixedBaseTable)
+        );
+    }
+
+    #[test]
+    fn test_11_public_key_over_the_wire() {
+        let agreement = agreement_g1();
+        let public_key = agreement.generate_public_key(5);
+        for compressed in [true, false] {
+            let bytes = public_key.to_sec1(compressed);
+            assert_eq!(agreement.public_key_from_sec1(&bytes).unwrap(), public_key);
+        }
+
+        // punto de la curva de orden 3: fuera del subgrupo de G
+        let small_order = PointEllipticCurve::new(
+            FiniteBody::new(43, 9),
+            FiniteBody::new(43, 2),
+            agreement.a,
+            agreement.b,
+        )
+        .unwrap()
+            * 13_usize;
+        assert_eq!(
+            agreement.public_key_from_sec1(&small_order.to_sec1(true)),
+            Err(EllipticCurveError::PointNotInSubgroup)
+        );
+        assert_eq!(
+            agreement.public_key_from_sec1(&[0x00]),
+            Err(EllipticCurveError::PointAtInfinity)
+        );
+    }
+
+    #[test]
+    fn test_12_scalar_private_keys() {
+        let agreement = agreement_g1();
+        let mut rng = rand::thread_rng();
+        let alice = agreement.random_private_key(&mut rng);
+        let bob = agreement.random_private_key(&mut rng);
+        assert_eq!(alice.order(), BigInteger::from(agreement.order_g()));
+        assert!(!alice.is_zero() && !bob.is_zero());
+
+        let secret_alice = agreement
+            .generate_shared_secret(agreement.generate_public_key(bob), alice)
+            .unwrap();
+        let secret_bob = agreement
+            .generate_shared_secret(agreement.generate_public_key(alice), bob)
+            .unwrap();
+        assert_eq!(secret_alice, secret_bob);
+        assert_eq!(secret_alice, agreement.g * (alice * bob));
+
+        // k y k + n son la misma clave
+        let n = agreement.order_g();
+        assert_eq!(
+            agreement.generate_public_key(alice),
+            agreement.generate_public_key(alice.value() + BigInteger::from(n))
+        );
+    }
+
+    #[test]
+    fn test_13_generator_order_larger_than_p() {
+        // y^2 = x^3 + x + 3 sobre F_43 tiene 47 puntos: G genera toda la curva y su orden pasa a p
+        let p = 43;
+        let (a, b) = (FiniteBody::new(p, 1), FiniteBody::new(p, 3));
+        let g =
+            PointEllipticCurve::new(FiniteBody::new(p, 2), FiniteBody::new(p, 20), a, b).unwrap();
+        let agreement = Agreement::new(p, a, b, g).unwrap();
+        assert_eq!(agreement.order_g(), 47);
+        assert_eq!(agreement.quantity_points_curve(), 47);
+
+        let (alice, bob) = (45_usize, 46_usize);
+        let secret_alice = agreement
+            .generate_shared_secret(agreement.generate_public_key(bob), alice)
+            .unwrap();
+        let secret_bob = agreement
+            .generate_shared_secret(agreement.generate_public_key(alice), bob)
+            .unwrap();
+        assert_eq!(secret_alice, secret_bob);
+    }
+
+    // El intercambio de los tests de arriba, escrito para cualquier backend
+    fn diffie_hellman_flow<D: DiffieHellman>(backend: &D) -> D::SharedSecret {
+        let mut rng = rand::thread_rng();
+
+        // ALICE y BOB eligen sus claves privadas y publican las claves publicas
+        let private_key_alice = backend.random_private_key(&mut rng);
+        let public_key_alice = backend.public_key(private_key_alice);
+        let private_key_bob = backend.random_private_key(&mut rng);
+        let public_key_bob = backend.public_key(private_key_bob);
+
+        // cada uno combina su clave privada con la publica del otro
+        let shared_secret_alice = backend
+            .shared_secret(public_key_bob, private_key_alice)
+            .unwrap();
+        let shared_secret_bob = backend
+            .shared_secret(public_key_alice, private_key_bob)
+            .unwrap();
+        assert_eq!(shared_secret_alice, shared_secret_bob);
+        shared_secret_alice
+    }
+
+    #[test]
+    fn test_14_diffie_hellman_backends() {
+        let agreement = agreement_g1();
+        let secret = diffie_hellman_flow(&agreement);
+        assert!(!secret.is_identity());
+
+        diffie_hellman_flow(&X25519);
+        diffie_hellman_flow(&X448);
+    }
+
+    #[test]
+    fn test_15_x25519_small_order_public_key_is_rejected() {
+        let private_key = X25519.random_private_key(&mut rand::thread_rng());
+        let mut small_order = [0; X25519_SIZE];
+        assert_eq!(
+            X25519.shared_secret(small_order, private_key),
+            Err(EllipticCurveError::PointAtInfinity)
+        );
+        small_order[0] = 1;
+        assert_eq!(
+            X25519.shared_secret(small_order, private_key),
+            Err(EllipticCurveError::PointAtInfinity)
+        );
+        assert_eq!(
+            X448.shared_secret(
+                [0; X448_SIZE],
+                X448.random_private_key(&mut rand::thread_rng())
+            ),
+            Err(EllipticCurveError::PointAtInfinity)
+        );
+    }
 }
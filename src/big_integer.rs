@@ -0,0 +1,681 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Neg, Rem, Shl, Shr, Sub};
+
+use num::traits::Pow;
+use num::{BigInt, Num, One, Zero};
+
+/// Cantidad de palabras de 64 bits. Alcanza para el producto de dos elementos de un cuerpo de 521 bits
+/// (P-521), que es el caso mas grande antes de reducir modulo p.
+const LIMBS: usize = 18;
+
+/// Entero con signo de precision fija (1152 bits) que implementa `Copy`.
+///
+/// `FiniteBody<T>` y `PointEllipticCurve<T>` piden que `T` sea `Copy`, por lo que no se puede usar
+/// `num::BigInt`. Este tipo se comporta como los enteros primitivos: division truncada hacia cero,
+/// resto con el signo del dividendo y panic ante un overflow.
+#[derive(Clone, Copy)]
+pub struct BigInteger {
+    negative: bool,
+    magnitude: [u64; LIMBS], // little endian
+}
+
+impl BigInteger {
+    fn from_parts(negative: bool, magnitude: [u64; LIMBS]) -> Self {
+        let is_zero = magnitude.iter().all(|limb| *limb == 0);
+        BigInteger {
+            negative: negative && !is_zero,
+            magnitude,
+        }
+    }
+
+    /// Interpreta los bytes como un entero sin signo big endian.
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let bytes = &bytes[bytes.iter().take_while(|b| **b == 0).count()..];
+        if bytes.len() > LIMBS * 8 {
+            panic!("attempt to build a BigInteger with overflow");
+        }
+        let mut magnitude = [0u64; LIMBS];
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            magnitude[i / 8] |= (*byte as u64) << (8 * (i % 8));
+        }
+        Self::from_parts(false, magnitude)
+    }
+
+    /// Bytes big endian del valor absoluto, sin ceros a la izquierda (vacio para el cero).
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let len = self.bits().div_ceil(8);
+        self.to_bytes_be_padded(len)
+    }
+
+    /// Bytes big endian del valor absoluto, completando con ceros hasta `len` bytes.
+    pub fn to_bytes_be_padded(&self, len: usize) -> Vec<u8> {
+        let needed = self.bits().div_ceil(8);
+        if needed > len {
+            panic!("BigInteger does not fit in {} bytes", len);
+        }
+        (0..len)
+            .rev()
+            .map(|i| {
+                if i / 8 < LIMBS {
+                    (self.magnitude[i / 8] >> (8 * (i % 8))) as u8
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+
+    /// Cantidad de bits significativos del valor absoluto.
+    pub fn bits(&self) -> usize {
+        let len = significant_limbs(&self.magnitude);
+        if len == 0 {
+            return 0;
+        }
+        64 * len - self.magnitude[len - 1].leading_zeros() as usize
+    }
+
+    /// Bit `i` del valor absoluto.
+    pub fn bit(&self, i: usize) -> bool {
+        i < 64 * LIMBS && (self.magnitude[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.magnitude[0] & 1 == 1
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.magnitude)
+    }
+
+    /// `b` si `choice` es true y `a` si no, combinando las palabras con una mascara en lugar de
+    /// bifurcar segun `choice`.
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let mask = (choice as u64).wrapping_neg();
+        let mut magnitude = a.magnitude;
+        for (limb, other) in magnitude.iter_mut().zip(b.magnitude.iter()) {
+            *limb ^= mask & (*limb ^ other);
+        }
+        BigInteger {
+            negative: a.negative ^ (choice & (a.negative ^ b.negative)),
+            magnitude,
+        }
+    }
+}
+
+fn significant_limbs(magnitude: &[u64]) -> usize {
+    magnitude
+        .iter()
+        .rposition(|limb| *limb != 0)
+        .map_or(0, |i| i + 1)
+}
+
+fn cmp_magnitude(a: &[u64; LIMBS], b: &[u64; LIMBS]) -> Ordering {
+    for i in (0..LIMBS).rev() {
+        match a[i].cmp(&b[i]) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn add_magnitude(a: &[u64; LIMBS], b: &[u64; LIMBS]) -> [u64; LIMBS] {
+    let mut result = [0u64; LIMBS];
+    let mut carry = 0u128;
+    for i in 0..LIMBS {
+        let sum = a[i] as u128 + b[i] as u128 + carry;
+        result[i] = sum as u64;
+        carry = sum >> 64;
+    }
+    if carry != 0 {
+        panic!("attempt to add with overflow");
+    }
+    result
+}
+
+// requiere a >= b
+fn sub_magnitude(a: &[u64; LIMBS], b: &[u64; LIMBS]) -> [u64; LIMBS] {
+    let mut result = [0u64; LIMBS];
+    let mut borrow = false;
+    for i in 0..LIMBS {
+        let (partial, borrow_1) = a[i].overflowing_sub(b[i]);
+        let (partial, borrow_2) = partial.overflowing_sub(borrow as u64);
+        result[i] = partial;
+        borrow = borrow_1 || borrow_2;
+    }
+    result
+}
+
+fn mul_magnitude(a: &[u64; LIMBS], b: &[u64; LIMBS]) -> [u64; LIMBS] {
+    let (len_a, len_b) = (significant_limbs(a), significant_limbs(b));
+    let mut result = [0u64; LIMBS];
+    for i in 0..len_a {
+        let mut carry = 0u128;
+        for j in 0..len_b {
+            let product = a[i] as u128 * b[j] as u128 + carry;
+            if i + j >= LIMBS {
+                if product != 0 {
+                    panic!("attempt to multiply with overflow");
+                }
+                continue;
+            }
+            let sum = result[i + j] as u128 + (product as u64) as u128;
+            result[i + j] = sum as u64;
+            carry = (product >> 64) + (sum >> 64);
+        }
+        if carry != 0 {
+            if i + len_b >= LIMBS {
+                panic!("attempt to multiply with overflow");
+            }
+            result[i + len_b] = carry as u64;
+        }
+    }
+    result
+}
+
+// Algoritmo D de Knuth (The Art of Computer Programming, Vol. 2, 4.3.1).
+fn div_rem_magnitude(u: &[u64; LIMBS], v: &[u64; LIMBS]) -> ([u64; LIMBS], [u64; LIMBS]) {
+    let n = significant_limbs(v);
+    if n == 0 {
+        panic!("attempt to divide by zero");
+    }
+    if cmp_magnitude(u, v) == Ordering::Less {
+        return ([0u64; LIMBS], *u);
+    }
+    let m = significant_limbs(u);
+    let mut quotient = [0u64; LIMBS];
+
+    if n == 1 {
+        let divisor = v[0] as u128;
+        let mut remainder = 0u128;
+        for i in (0..m).rev() {
+            let current = (remainder << 64) | u[i] as u128;
+            quotient[i] = (current / divisor) as u64;
+            remainder = current % divisor;
+        }
+        let mut rem = [0u64; LIMBS];
+        rem[0] = remainder as u64;
+        return (quotient, rem);
+    }
+
+    // normalizacion: el bit mas alto del divisor queda en 1
+    let shift = v[n - 1].leading_zeros();
+    let mut vn = [0u64; LIMBS];
+    let mut un = [0u64; LIMBS + 1];
+    for i in (0..n).rev() {
+        vn[i] = v[i] << shift;
+        if shift > 0 && i > 0 {
+            vn[i] |= v[i - 1] >> (64 - shift);
+        }
+    }
+    un[m] = if shift > 0 {
+        u[m - 1] >> (64 - shift)
+    } else {
+        0
+    };
+    for i in (0..m).rev() {
+        un[i] = u[i] << shift;
+        if shift > 0 && i > 0 {
+            un[i] |= u[i - 1] >> (64 - shift);
+        }
+    }
+
+    let base = 1u128 << 64;
+    for j in (0..=(m - n)).rev() {
+        let numerator = ((un[j + n] as u128) << 64) | un[j + n - 1] as u128;
+        let mut q_hat = numerator / vn[n - 1] as u128;
+        let mut r_hat = numerator % vn[n - 1] as u128;
+        while q_hat >= base || q_hat * vn[n - 2] as u128 > ((r_hat << 64) | un[j + n - 2] as u128) {
+            q_hat -= 1;
+            r_hat += vn[n - 1] as u128;
+            if r_hat >= base {
+                break;
+            }
+        }
+
+        let mut borrow = 0i128;
+        let mut carry = 0u128;
+        for i in 0..n {
+            let product = q_hat * vn[i] as u128 + carry;
+            carry = product >> 64;
+            let t = un[i + j] as i128 - borrow - (product as u64) as i128;
+            un[i + j] = t as u64;
+            borrow = (t < 0) as i128;
+        }
+        let t = un[j + n] as i128 - borrow - carry as i128;
+        un[j + n] = t as u64;
+
+        quotient[j] = q_hat as u64;
+        if t < 0 {
+            quotient[j] -= 1;
+            let mut carry = 0u128;
+            for i in 0..n {
+                let sum = un[i + j] as u128 + vn[i] as u128 + carry;
+                un[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+    }
+
+    let mut remainder = [0u64; LIMBS];
+    for i in 0..n {
+        remainder[i] = un[i] >> shift;
+        if shift > 0 {
+            remainder[i] |= un[i + 1] << (64 - shift);
+        }
+    }
+    (quotient, remainder)
+}
+
+impl Add for BigInteger {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.negative == other.negative {
+            return Self::from_parts(
+                self.negative,
+                add_magnitude(&self.magnitude, &other.magnitude),
+            );
+        }
+        match cmp_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => Self::from_parts(
+                other.negative,
+                sub_magnitude(&other.magnitude, &self.magnitude),
+            ),
+            _ => Self::from_parts(
+                self.negative,
+                sub_magnitude(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for BigInteger {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl Neg for BigInteger {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl Mul for BigInteger {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::from_parts(
+            self.negative != other.negative,
+            mul_magnitude(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl Div for BigInteger {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        let (quotient, _) = div_rem_magnitude(&self.magnitude, &other.magnitude);
+        Self::from_parts(self.negative != other.negative, quotient)
+    }
+}
+
+impl Rem for BigInteger {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self::Output {
+        let (_, remainder) = div_rem_magnitude(&self.magnitude, &other.magnitude);
+        Self::from_parts(self.negative, remainder)
+    }
+}
+
+impl Shl<usize> for BigInteger {
+    type Output = Self;
+
+    fn shl(self, shift: usize) -> Self::Output {
+        if self.bits() + shift > 64 * LIMBS {
+            panic!("attempt to shift left with overflow");
+        }
+        let (limbs, bits) = (shift / 64, shift % 64);
+        let mut magnitude = [0u64; LIMBS];
+        for i in (limbs..LIMBS).rev() {
+            magnitude[i] = self.magnitude[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                magnitude[i] |= self.magnitude[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        Self::from_parts(self.negative, magnitude)
+    }
+}
+
+impl Shr<usize> for BigInteger {
+    type Output = Self;
+
+    /// Desplaza el valor absoluto (redondea hacia cero, como la division por 2^shift).
+    fn shr(self, shift: usize) -> Self::Output {
+        let (limbs, bits) = (shift / 64, shift % 64);
+        let mut magnitude = [0u64; LIMBS];
+        for (i, limb) in magnitude
+            .iter_mut()
+            .enumerate()
+            .take(LIMBS.saturating_sub(limbs))
+        {
+            *limb = self.magnitude[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < LIMBS {
+                *limb |= self.magnitude[i + limbs + 1] << (64 - bits);
+            }
+        }
+        Self::from_parts(self.negative, magnitude)
+    }
+}
+
+impl Pow<usize> for BigInteger {
+    type Output = Self;
+
+    fn pow(self, mut exponent: usize) -> Self::Output {
+        let mut result = Self::one();
+        let mut base = self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base * base;
+            }
+        }
+        result
+    }
+}
+
+impl Zero for BigInteger {
+    fn zero() -> Self {
+        Self::from_parts(false, [0u64; LIMBS])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.magnitude.iter().all(|limb| *limb == 0)
+    }
+}
+
+impl One for BigInteger {
+    fn one() -> Self {
+        Self::from(1u64)
+    }
+}
+
+impl Num for BigInteger {
+    type FromStrRadixErr = String;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let value = BigInt::from_str_radix(str, radix).map_err(|e| e.to_string())?;
+        BigInteger::try_from(&value)
+    }
+}
+
+impl PartialEq for BigInteger {
+    fn eq(&self, other: &Self) -> bool {
+        self.negative == other.negative && self.magnitude == other.magnitude
+    }
+}
+
+impl Eq for BigInteger {}
+
+impl Hash for BigInteger {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.negative.hash(state);
+        self.magnitude.hash(state);
+    }
+}
+
+impl Ord for BigInteger {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInteger {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", BigInt::from(*self))
+    }
+}
+
+impl fmt::Debug for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::LowerHex for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.negative { "-" } else { "" };
+        write!(f, "{}{}", sign, BigInt::from(self.abs()).to_str_radix(16))
+    }
+}
+
+impl From<BigInteger> for BigInt {
+    fn from(value: BigInteger) -> Self {
+        let bigint = BigInt::from_bytes_be(num::bigint::Sign::Plus, &value.to_bytes_be());
+        if value.negative {
+            -bigint
+        } else {
+            bigint
+        }
+    }
+}
+
+impl TryFrom<&BigInt> for BigInteger {
+    type Error = String;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        let (sign, bytes) = value.to_bytes_be();
+        if bytes.len() > LIMBS * 8 {
+            return Err(format!("{} does not fit in a BigInteger", value));
+        }
+        let magnitude = BigInteger::from_bytes_be(&bytes);
+        Ok(Self::from_parts(
+            sign == num::bigint::Sign::Minus,
+            magnitude.magnitude,
+        ))
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInteger {
+                #[allow(unused_comparisons)]
+                fn from(value: $t) -> Self {
+                    let magnitude = (value as i128).unsigned_abs();
+                    let mut limbs = [0u64; LIMBS];
+                    limbs[0] = magnitude as u64;
+                    limbs[1] = (magnitude >> 64) as u64;
+                    Self::from_parts(value < 0, limbs)
+                }
+            }
+
+            impl TryFrom<BigInteger> for $t {
+                type Error = String;
+
+                fn try_from(value: BigInteger) -> Result<Self, Self::Error> {
+                    if significant_limbs(&value.magnitude) > 2 {
+                        return Err(format!("{} does not fit in {}", value, stringify!($t)));
+                    }
+                    let magnitude = value.magnitude[0] as u128 | (value.magnitude[1] as u128) << 64;
+                    let converted = if value.negative {
+                        i128::try_from(magnitude).ok().and_then(|m| <$t>::try_from(-m).ok())
+                    } else {
+                        <$t>::try_from(magnitude).ok()
+                    };
+                    converted.ok_or_else(|| format!("{} does not fit in {}", value, stringify!($t)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_primitive!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<i128> for BigInteger {
+    fn from(value: i128) -> Self {
+        let magnitude = value.unsigned_abs();
+        let mut limbs = [0u64; LIMBS];
+        limbs[0] = magnitude as u64;
+        limbs[1] = (magnitude >> 64) as u64;
+        Self::from_parts(value < 0, limbs)
+    }
+}
+
+impl TryFrom<BigInteger> for i128 {
+    type Error = String;
+
+    fn try_from(value: BigInteger) -> Result<Self, Self::Error> {
+        if significant_limbs(&value.magnitude) > 2 {
+            return Err(format!("{} does not fit in i128", value));
+        }
+        let magnitude = value.magnitude[0] as u128 | (value.magnitude[1] as u128) << 64;
+        if value.negative {
+            if magnitude == 1u128 << 127 {
+                return Ok(i128::MIN);
+            }
+            i128::try_from(magnitude)
+                .map(|m| -m)
+                .map_err(|_| format!("{} does not fit in i128", value))
+        } else {
+            i128::try_from(magnitude).map_err(|_| format!("{} does not fit in i128", value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_big_integer {
+    use rand::Rng;
+
+    use super::*;
+
+    fn random_big_int(rng: &mut impl Rng, bytes: usize) -> BigInt {
+        let bytes: Vec<u8> = (0..bytes).map(|_| rng.gen()).collect();
+        let value = BigInt::from_bytes_be(num::bigint::Sign::Plus, &bytes);
+        if rng.gen() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    #[test]
+    fn test_01_small_values_behave_like_primitives() {
+        for (a, b) in [(17i64, 5i64), (-17, 5), (17, -5), (-17, -5), (0, 3), (4, 9)] {
+            let (x, y) = (BigInteger::from(a), BigInteger::from(b));
+            assert_eq!(x + y, BigInteger::from(a + b));
+            assert_eq!(x - y, BigInteger::from(a - b));
+            assert_eq!(x * y, BigInteger::from(a * b));
+            assert_eq!(x / y, BigInteger::from(a / b));
+            assert_eq!(x % y, BigInteger::from(a % b));
+            assert_eq!(x < y, a < b);
+        }
+    }
+
+    #[test]
+    fn test_02_random_values_match_num_bigint() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..500 {
+            let len_a = rng.gen_range(1..70);
+            let len_b = rng.gen_range(1..70);
+            let a = random_big_int(&mut rng, len_a);
+            let b = random_big_int(&mut rng, len_b);
+            let (x, y) = (
+                BigInteger::try_from(&a).unwrap(),
+                BigInteger::try_from(&b).unwrap(),
+            );
+
+            assert_eq!(BigInt::from(x + y), &a + &b);
+            assert_eq!(BigInt::from(x - y), &a - &b);
+            assert_eq!(BigInt::from(x * y), &a * &b);
+            if !b.is_zero() {
+                assert_eq!(BigInt::from(x / y), &a / &b);
+                assert_eq!(BigInt::from(x % y), &a % &b);
+            }
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+        }
+    }
+
+    #[test]
+    fn test_03_parse_and_format() {
+        let p = BigInteger::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        assert_eq!(p.bits(), 256);
+        assert_eq!(
+            format!("{:x}", p),
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+        );
+        assert_eq!(BigInteger::from(-1021).to_string(), "-1021");
+        assert_eq!(BigInteger::from_bytes_be(&p.to_bytes_be()), p);
+        assert_eq!(BigInteger::from(1).to_bytes_be_padded(3), vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_04_shifts_and_bits() {
+        let one = BigInteger::one();
+        let x = one << 200;
+        assert_eq!(x.bits(), 201);
+        assert!(x.bit(200));
+        assert!(!x.bit(199));
+        assert_eq!(x >> 200, one);
+        assert_eq!(BigInteger::from(2).pow(200), x);
+    }
+
+    #[test]
+    fn test_05_conversions_to_primitives() {
+        assert_eq!(i32::try_from(BigInteger::from(-43)).unwrap(), -43);
+        assert_eq!(
+            i128::try_from(BigInteger::from(i128::MIN)).unwrap(),
+            i128::MIN
+        );
+        assert!(i32::try_from(BigInteger::from(1u64 << 40)).is_err());
+        assert!(usize::try_from(BigInteger::from(-1)).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_06_overflow_panics() {
+        let x = BigInteger::one() << 1000;
+        let _ = x * x;
+    }
+
+    #[test]
+    fn test_07_conditional_select() {
+        let a = BigInteger::from(-5) << 200;
+        let b = BigInteger::from(1234567);
+        assert_eq!(BigInteger::conditional_select(&a, &b, false), a);
+        assert_eq!(BigInteger::conditional_select(&a, &b, true), b);
+        assert_eq!(BigInteger::conditional_select(&b, &a, true), a);
+    }
+}
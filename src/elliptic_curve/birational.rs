@@ -0,0 +1,428 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{
+    big_integer::BigInteger,
+    finite_body::{finite_body::FiniteBody, polynomial::Polynomial},
+};
+
+use super::{
+    curve::{one_like, small_multiple, zero_like, Curve},
+    edwards::{EdwardsCurve, EdwardsPoint},
+    montgomery::{MontgomeryCurve, MontgomeryPoint},
+    point_elliptic_curve::{Coordinates, PointEllipticCurve},
+};
+
+// Equivalencias entre las tres formas de una curva:
+//
+//   Weierstrass  y^2 = x^3 + ax + b
+//   Montgomery   By^2 = x^3 + Ax^2 + x
+//   Edwards      ax^2 + y^2 = 1 + dx^2y^2
+//
+// Montgomery -> Weierstrass es un isomorfismo (un cambio de variables lineal). Montgomery ->
+// Edwards es birracional: los puntos con v = 0 (salvo (0, 0)) y con u = -1 van a puntos del infinito
+// de la curva de Edwards, que no tienen coordenadas afines. Si la curva de Edwards es completa esos
+// puntos no estan en F_p. Los mapas de puntos son homomorfismos de grupo donde estan definidos.
+
+impl<T> MontgomeryCurve<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// Curva de Weierstrass isomorfa y^2 = x^3 + ax + b, con a = (3 - A^2) / 3B^2 y
+    /// b = (2A^3 - 9A) / 27B^3. Necesita p != 3.
+    pub fn to_weierstrass(&self) -> Curve<T> {
+        let (a, b) = (self.a, self.b);
+        let one = one_like(a);
+        let (b2, b3) = (b * b, b * b * b);
+        Curve {
+            a: (small_multiple(one, 3) - a * a) / small_multiple(b2, 3),
+            b: (small_multiple(a * a * a, 2) - small_multiple(a, 9)) / small_multiple(b3, 27),
+        }
+    }
+
+    /// Curva de Edwards torcida birracionalmente equivalente: a = (A + 2) / B, d = (A - 2) / B. Es
+    /// la inversa de `EdwardsCurve::to_montgomery`.
+    pub fn to_edwards(&self) -> EdwardsCurve<T> {
+        let two = small_multiple(one_like(self.a), 2);
+        EdwardsCurve {
+            a: (self.a + two) / self.b,
+            d: (self.a - two) / self.b,
+        }
+    }
+
+    /// El punto de esta curva que va a `point` por `MontgomeryPoint::to_weierstrass`:
+    /// (x, y) -> (B x - A / 3, B y). None si `point` no es de `to_weierstrass()`.
+    pub fn from_weierstrass(&self, point: &PointEllipticCurve<T>) -> Option<MontgomeryPoint<T>> {
+        let weierstrass = self.to_weierstrass();
+        if point.a != weierstrass.a || point.b != weierstrass.b {
+            return None;
+        }
+        match point.coordinates {
+            Coordinates::Identity => Some(self.identity()),
+            Coordinates::Affine { x, y } => {
+                let shift = self.a / small_multiple(one_like(x), 3);
+                self.point(self.b * x - shift, self.b * y)
+            }
+        }
+    }
+}
+
+impl<T> MontgomeryPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// El punto en `MontgomeryCurve::to_weierstrass`: (u, v) -> (u / B + A / 3B, v / B), y el
+    /// punto del infinito en el punto del infinito.
+    pub fn to_weierstrass(&self) -> PointEllipticCurve<T> {
+        let weierstrass = self.curve().to_weierstrass();
+        let coordinates = match self.coordinates {
+            Coordinates::Identity => Coordinates::Identity,
+            Coordinates::Affine { x: u, y: v } => Coordinates::Affine {
+                x: (u + self.a / small_multiple(one_like(u), 3)) / self.b,
+                y: v / self.b,
+            },
+        };
+        PointEllipticCurve {
+            coordinates,
+            a: weierstrass.a,
+            b: weierstrass.b,
+        }
+    }
+
+    /// El punto en `MontgomeryCurve::to_edwards`: (u, v) -> (u / v, (u - 1) / (u + 1)). El punto
+    /// del infinito va al neutro (0, 1) y (0, 0) a (0, -1), los dos puntos con x = 0. None para los
+    /// que van a puntos del infinito de la curva de Edwards: v = 0 con u != 0, o u = -1.
+    pub fn to_edwards(&self) -> Option<EdwardsPoint<T>> {
+        let edwards = self.curve().to_edwards();
+        let Some((u, v)) = self.coordinates() else {
+            return Some(edwards.identity());
+        };
+        let one = one_like(u);
+        if u.is_zero() {
+            return edwards.point(zero_like(u), -one);
+        }
+        if v.is_zero() || (u + one).is_zero() {
+            return None;
+        }
+        edwards.point(u / v, (u - one) / (u + one))
+    }
+
+    fn curve(&self) -> MontgomeryCurve<T> {
+        MontgomeryCurve {
+            a: self.a,
+            b: self.b,
+        }
+    }
+}
+
+impl<T> EdwardsCurve<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// Curva de Montgomery birracionalmente equivalente: A = 2(a + d) / (a - d), B = 4 / (a - d).
+    pub fn to_montgomery(&self) -> MontgomeryCurve<T> {
+        let a_minus_d = self.a - self.d;
+        MontgomeryCurve {
+            a: small_multiple(self.a + self.d, 2) / a_minus_d,
+            b: small_multiple(one_like(self.a), 4) / a_minus_d,
+        }
+    }
+
+    /// `to_montgomery().to_weierstrass()`.
+    pub fn to_weierstrass(&self) -> Curve<T> {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    /// El punto de esta curva que va a `point` por `EdwardsPoint::to_weierstrass`. None si `point`
+    /// no es de `to_weierstrass()` o si corresponde a un punto del infinito de la curva de Edwards
+    /// (ver `MontgomeryPoint::to_edwards`).
+    pub fn from_weierstrass(&self, point: &PointEllipticCurve<T>) -> Option<EdwardsPoint<T>> {
+        self.to_montgomery().from_weierstrass(point)?.to_edwards()
+    }
+}
+
+impl<T> EdwardsPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// El punto en `EdwardsCurve::to_montgomery`: (x, y) -> ((1 + y) / (1 - y), u / x). El neutro
+    /// va al punto del infinito y (0, -1), el punto de orden 2, a (0, 0).
+    pub fn to_montgomery(&self) -> MontgomeryPoint<T> {
+        let curve = EdwardsCurve {
+            a: self.a,
+            d: self.d,
+        }
+        .to_montgomery();
+        let Some(u) = self.to_montgomery_u() else {
+            return curve.identity();
+        };
+        let (x, _) = self.to_affine();
+        let v = match x.is_zero() {
+            true => zero_like(x),
+            false => u / x,
+        };
+        MontgomeryPoint {
+            coordinates: Coordinates::Affine { x: u, y: v },
+            a: curve.a,
+            b: curve.b,
+        }
+    }
+
+    /// `to_montgomery().to_weierstrass()`: el neutro va al punto del infinito y (0, -1) a
+    /// (A / 3B, 0).
+    pub fn to_weierstrass(&self) -> PointEllipticCurve<T> {
+        self.to_montgomery().to_weierstrass()
+    }
+}
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>,
+{
+    /// Una forma de Montgomery de la curva, si existe: hace falta un punto (α, 0) de orden 2 con
+    /// 3α^2 + a un cuadrado, y entonces con s = 1 / sqrt(3α^2 + a) la curva es isomorfa a
+    /// By^2 = x^3 + Ax^2 + x con A = 3αs y B = s. Se prueba con cada raiz α de x^3 + ax + b.
+    /// Cumple `to_montgomery().unwrap().to_weierstrass() == self`.
+    pub fn to_montgomery(&self) -> Option<MontgomeryCurve<FiniteBody<X>>> {
+        let p = self.a.p;
+        let (zero, one) = (FiniteBody::new(p, X::zero()), FiniteBody::new(p, X::one()));
+        let cubic = Polynomial::from_coefficients(p, vec![self.b, self.a, zero, one]);
+        cubic.roots().into_iter().find_map(|alpha| {
+            let root = (small_multiple(alpha * alpha, 3) + self.a).sqrt()?;
+            let s = root.inverse()?;
+            Some(MontgomeryCurve {
+                a: small_multiple(alpha * s, 3),
+                b: s,
+            })
+        })
+    }
+
+    /// Una forma de Edwards torcida de la curva: `to_montgomery()` y despues
+    /// `MontgomeryCurve::to_edwards`. None si la curva no tiene forma de Montgomery.
+    pub fn to_edwards(&self) -> Option<EdwardsCurve<FiniteBody<X>>> {
+        self.to_montgomery().map(|curve| curve.to_edwards())
+    }
+}
+
+#[cfg(test)]
+mod test_birational {
+    use rand::{seq::SliceRandom, Rng};
+
+    use super::*;
+    use crate::elliptic_curve::edwards::{ed25519, ed25519_base_point, ed25519_order};
+
+    type Point = PointEllipticCurve<FiniteBody<i64>>;
+
+    fn curve(p: i64, a: i64, b: i64) -> Curve<FiniteBody<i64>> {
+        Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap()
+    }
+
+    // Pares de puntos al azar de la curva, incluidos O, los puntos de orden 2, P + P y P + (-P)
+    fn random_pairs(points: &[Point], count: usize) -> Vec<(Point, Point)> {
+        let mut rng = rand::thread_rng();
+        let mut pairs: Vec<_> = (0..count)
+            .map(|_| {
+                (
+                    *points.choose(&mut rng).unwrap(),
+                    *points.choose(&mut rng).unwrap(),
+                )
+            })
+            .collect();
+        for point in points {
+            if point.is_identity() || point.y().unwrap().value == 0 || rng.gen_bool(0.1) {
+                pairs.push((*point, *point));
+                pairs.push((*point, -*point));
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_01_curve_parameters() {
+        // y^2 = x^3 - x sobre F_1009 tiene los tres puntos de orden 2 y forma de Montgomery
+        let weierstrass = curve(1009, -1, 0);
+        let montgomery = weierstrass.to_montgomery().unwrap();
+        assert_eq!(montgomery.to_weierstrass(), weierstrass);
+        let edwards = weierstrass.to_edwards().unwrap();
+        assert_eq!(edwards.to_montgomery(), montgomery);
+        assert_eq!(montgomery.to_edwards(), edwards);
+        assert_eq!(edwards.to_weierstrass(), weierstrass);
+
+        // y^2 = x^3 + 7 sobre F_43 no tiene puntos de orden 2 (x^3 = -7 no tiene raices)
+        assert_eq!(curve(43, 0, 7).to_montgomery(), None);
+        assert_eq!(curve(43, 0, 7).to_edwards(), None);
+        // sobre F_1019 los tres puntos de orden 2 de y^2 = x^3 - x dan 3α^2 - 1 = -1, 2, 2, que no
+        // son cuadrados
+        assert_eq!(curve(1019, -1, 0).to_montgomery(), None);
+
+        // Curve25519 y Ed25519 en su forma de Weierstrass
+        let ed25519 = ed25519();
+        let weierstrass = ed25519.to_weierstrass();
+        let montgomery = weierstrass.to_montgomery().unwrap();
+        assert_eq!(montgomery.to_weierstrass(), weierstrass);
+        // A queda determinado salvo el signo: (A, B) y (-A, -B) son isomorfas por u -> -u
+        let a = ed25519.to_montgomery().a;
+        assert!(montgomery.a == a || montgomery.a == -a);
+    }
+
+    #[test]
+    fn test_02_identity_and_exceptional_points() {
+        let weierstrass = curve(1009, -1, 0);
+        let montgomery = weierstrass.to_montgomery().unwrap();
+        let edwards = montgomery.to_edwards();
+
+        // O <-> O <-> (0, 1)
+        let identity = montgomery
+            .from_weierstrass(&weierstrass.identity())
+            .unwrap();
+        assert!(identity.is_identity());
+        assert!(identity.to_weierstrass().is_identity());
+        assert_eq!(identity.to_edwards(), Some(edwards.identity()));
+        assert!(edwards.identity().to_montgomery().is_identity());
+
+        // (0, 0) <-> (0, -1)
+        let zero = FiniteBody::new(1009, 0);
+        let order_two = montgomery.point(zero, zero).unwrap();
+        let minus_one = edwards.point(zero, FiniteBody::new(1009, -1)).unwrap();
+        assert_eq!(order_two.to_edwards(), Some(minus_one));
+        assert_eq!(minus_one.to_montgomery(), order_two);
+        assert_eq!(
+            montgomery.from_weierstrass(&order_two.to_weierstrass()),
+            Some(order_two)
+        );
+
+        // los otros dos puntos de orden 2 tienen v = 0: van a puntos del infinito de Edwards
+        let other_roots: Vec<_> = weierstrass
+            .points()
+            .filter(|point| point.y().is_some_and(|y| y.value == 0))
+            .map(|point| montgomery.from_weierstrass(&point).unwrap())
+            .filter(|point| point.coordinates().unwrap().0.value != 0)
+            .collect();
+        assert_eq!(other_roots.len(), 2);
+        assert!(other_roots.iter().all(|point| point.to_edwards().is_none()));
+        assert!(!edwards.is_complete());
+
+        // un punto de otra curva no se convierte
+        let other = curve(1009, -1, 1).points().nth(1).unwrap();
+        assert_eq!(montgomery.from_weierstrass(&other), None);
+        assert_eq!(edwards.from_weierstrass(&other), None);
+    }
+
+    #[test]
+    fn test_03_maps_are_homomorphisms() {
+        for (p, a, b) in [(1009, -1, 0), (1021, 3, 2), (1033, 3, 2)] {
+            let weierstrass = curve(p, a, b);
+            let montgomery = weierstrass.to_montgomery().unwrap();
+            let edwards = montgomery.to_edwards();
+            let points: Vec<_> = weierstrass.points().collect();
+
+            for (p1, p2) in random_pairs(&points, 200) {
+                let sum = (p1 + p2).unwrap();
+
+                // Weierstrass -> Montgomery -> Weierstrass
+                let (m1, m2) = (
+                    montgomery.from_weierstrass(&p1).unwrap(),
+                    montgomery.from_weierstrass(&p2).unwrap(),
+                );
+                assert_eq!(montgomery.from_weierstrass(&sum), Some(m1 + m2));
+                assert_eq!((m1 + m2).to_weierstrass(), sum);
+
+                // Montgomery -> Edwards, donde esta definido. Estas curvas de Edwards no son
+                // completas y la suma unificada da Z = 0 para algunos pares
+                if let (Some(e1), Some(e2), Some(e_sum)) =
+                    (m1.to_edwards(), m2.to_edwards(), (m1 + m2).to_edwards())
+                {
+                    if (e1 + e2).z.value == 0 {
+                        assert!(!edwards.is_complete());
+                        continue;
+                    }
+                    assert_eq!(e1 + e2, e_sum);
+                    assert_eq!((e1 + e2).to_montgomery(), m1 + m2);
+                    assert_eq!((e1 + e2).to_weierstrass(), sum);
+                    assert_eq!(edwards.from_weierstrass(&sum), Some(e1 + e2));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_04_complete_edwards_curve() {
+        // Edwards completa: todos los puntos de Weierstrass tienen imagen y la suma se preserva
+        let edwards = EdwardsCurve::new(FiniteBody::new(101, -1), FiniteBody::new(101, 2)).unwrap();
+        let weierstrass = edwards.to_weierstrass();
+        let points: Vec<_> = weierstrass.points().collect();
+        for (p1, p2) in random_pairs(&points, 300) {
+            let (e1, e2) = (
+                edwards.from_weierstrass(&p1).unwrap(),
+                edwards.from_weierstrass(&p2).unwrap(),
+            );
+            assert_eq!((e1 + e2).to_weierstrass(), (p1 + p2).unwrap());
+            assert_eq!(
+                e1.to_montgomery() + e2.to_montgomery(),
+                (e1 + e2).to_montgomery()
+            );
+        }
+    }
+
+    #[test]
+    fn test_05_ed25519_homomorphism() {
+        let base = ed25519_base_point();
+        let order = ed25519_order();
+        let mut rng = rand::thread_rng();
+        let k1 = BigInteger::from(rng.gen::<u64>());
+        let k2 = order - BigInteger::from(rng.gen::<u32>());
+        let (e1, e2) = (base.mul_scalar(k1), base.mul_scalar(k2));
+
+        let (m1, m2) = (e1.to_montgomery(), e2.to_montgomery());
+        assert_eq!((e1 + e2).to_montgomery(), m1 + m2);
+        assert_eq!(
+            (e1 + e2).to_weierstrass(),
+            (e1.to_weierstrass() + e2.to_weierstrass()).unwrap()
+        );
+        assert_eq!((m1 + m2).to_edwards(), Some(e1 + e2));
+    }
+}
@@ -0,0 +1,234 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use super::{error::EllipticCurveError, point_elliptic_curve::PointEllipticCurve};
+
+/// Curva eliptica en forma de Weierstrass reducida: y^2 = x^3 + ax + b.
+///
+/// Los puntos llevan consigo los coeficientes `a` y `b`, esta estructura sirve para agrupar los
+/// parametros de la curva y construir puntos sobre ella.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Curve<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> Curve<T>
+where
+    T: Add<T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Div<T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Neg<Output = T>
+        + Zero
+        + One,
+{
+    /// Curva y^2 = x^3 + ax + b. Falla si la curva es singular (4a^3 + 27b^2 = 0).
+    pub fn new(a: T, b: T) -> Result<Self, EllipticCurveError> {
+        if is_singular(a, b) {
+            return Err(EllipticCurveError::SingularCurve);
+        }
+        Ok(Curve { a, b })
+    }
+
+    /// Discriminante -16(4a^3 + 27b^2). La curva es no singular si y solo si es distinto de cero.
+    pub fn discriminant(&self) -> T {
+        -small_multiple(singularity_term(self.a, self.b), 16)
+    }
+
+    /// Punto (x, y) de la curva, o None si no satisface la ecuacion. La singularidad ya se miro en
+    /// `new`.
+    pub fn point(&self, x: T, y: T) -> Option<PointEllipticCurve<T>> {
+        PointEllipticCurve::on_curve(x, y, self.a, self.b)
+    }
+
+    /// Punto del infinito, el neutro del grupo.
+    pub fn identity(&self) -> PointEllipticCurve<T> {
+        PointEllipticCurve::new_inf(self.a, self.b)
+    }
+
+    pub fn contains(&self, point: &PointEllipticCurve<T>) -> bool {
+        point.a == self.a && point.b == self.b
+    }
+}
+
+impl<T> Curve<T>
+where
+    T: Add<T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Div<Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// j-invariante 1728 * 4a^3 / (4a^3 + 27b^2). Dos curvas isomorfas sobre la clausura algebraica
+    /// tienen el mismo j-invariante. None si la curva es singular.
+    pub fn j_invariant(&self) -> Option<T> {
+        let denominator = singularity_term(self.a, self.b);
+        if denominator.is_zero() {
+            return None;
+        }
+        Some(small_multiple(small_multiple(self.a.pow(3), 4), 1728) / denominator)
+    }
+}
+
+/// value + value + ... + value (k veces) con "double and add". Sirve para las constantes de las
+/// formulas sin depender de `T::one()`, que en `FiniteBody` no conoce el modulo.
+pub(crate) fn small_multiple<T>(value: T, k: usize) -> T
+where
+    T: Add<Output = T> + Copy + Zero,
+{
+    let mut result = T::zero();
+    let mut doubled = value;
+    let mut k = k;
+    while k > 0 {
+        if k & 1 == 1 {
+            result = if result.is_zero() {
+                doubled
+            } else {
+                result + doubled
+            };
+        }
+        doubled = doubled + doubled;
+        k >>= 1;
+    }
+    result
+}
+
+/// Cero del mismo cuerpo que `reference`.
+pub(crate) fn zero_like<T>(reference: T) -> T
+where
+    T: Add<Output = T> + Neg<Output = T> + Copy,
+{
+    reference + (-reference)
+}
+
+/// Uno del mismo cuerpo que `reference`: `T::one()` se suma a la derecha, asi se usa el modulo de
+/// `reference`.
+pub(crate) fn one_like<T>(reference: T) -> T
+where
+    T: Add<Output = T> + Neg<Output = T> + Copy + One,
+{
+    zero_like(reference) + T::one()
+}
+
+/// Intercambia `a` y `b` si `choice` es 1 y los deja igual si es 0, sin bifurcar segun `choice`:
+/// d = choice * (a - b), a' = a - d, b' = b + d.
+pub(crate) fn conditional_swap<T>(a: &mut T, b: &mut T, choice: T)
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy,
+{
+    let difference = choice * (*a - *b);
+    *a = *a - difference;
+    *b = *b + difference;
+}
+
+/// true si la division de T es exacta, como en un cuerpo (`FiniteBody`, flotantes): 1/2 + 1/2 = 1.
+/// Con enteros la division trunca y las formulas jacobianas dan puntos fuera de la curva.
+pub(crate) fn has_exact_division<T>(reference: T) -> bool
+where
+    T: Add<Output = T> + Div<Output = T> + Neg<Output = T> + PartialEq + Copy + Zero + One,
+{
+    let one = one_like(reference);
+    let two = one + one;
+    two.is_zero() || one / two + one / two == one
+}
+
+/// 4a^3 + 27b^2, se anula cuando x^3 + ax + b tiene una raiz multiple.
+fn singularity_term<T>(a: T, b: T) -> T
+where
+    T: Add<Output = T> + Pow<usize, Output = T> + Copy + Zero,
+{
+    small_multiple(a.pow(3), 4) + small_multiple(b.pow(2), 27)
+}
+
+pub(crate) fn is_singular<T>(a: T, b: T) -> bool
+where
+    T: Add<Output = T> + Pow<usize, Output = T> + Copy + Zero,
+{
+    singularity_term(a, b).is_zero()
+}
+
+#[cfg(test)]
+mod test_curve {
+    use crate::finite_body::finite_body::FiniteBody;
+
+    use super::*;
+
+    #[test]
+    fn test_01_point_on_curve() {
+        let p = 43;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).unwrap();
+
+        let g1 = curve.point(FiniteBody::new(p, 13), FiniteBody::new(p, 15));
+        assert!(g1.is_some());
+        assert!(curve.contains(&g1.unwrap()));
+        assert!(curve
+            .point(FiniteBody::new(p, 13), FiniteBody::new(p, 16))
+            .is_none());
+    }
+
+    #[test]
+    fn test_02_identity() {
+        let p = 43;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).unwrap();
+        let g1 = curve
+            .point(FiniteBody::new(p, 13), FiniteBody::new(p, 15))
+            .unwrap();
+
+        assert_eq!(g1 * 13, curve.identity());
+        assert_eq!((g1 + curve.identity()).unwrap(), g1);
+    }
+
+    #[test]
+    fn test_03_singular_curve_is_rejected() {
+        let p = 43;
+        assert_eq!(
+            Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 0)),
+            Err(EllipticCurveError::SingularCurve)
+        );
+        // y^2 = x^3 - 3x + 2 = (x - 1)^2 (x + 2)
+        assert_eq!(
+            Curve::new(FiniteBody::new(p, -3), FiniteBody::new(p, 2)),
+            Err(EllipticCurveError::SingularCurve)
+        );
+    }
+
+    #[test]
+    fn test_04_discriminant() {
+        let p = 1021;
+        let curve = Curve::new(FiniteBody::new(p, -3), FiniteBody::new(p, -3)).unwrap();
+        // -16 * (4 * (-27) + 27 * 9) = -2160
+        assert_eq!(curve.discriminant(), FiniteBody::new(p, -2160));
+
+        assert_eq!(
+            Curve::new(5, 7).unwrap().discriminant(),
+            -16 * (4 * 125 + 27 * 49)
+        );
+    }
+
+    #[test]
+    fn test_05_j_invariant() {
+        let p = 43;
+        // a = 0 => j = 0
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).unwrap();
+        assert_eq!(curve.j_invariant().unwrap(), 0);
+
+        // b = 0 => j = 1728
+        let curve = Curve::new(FiniteBody::new(p, 5), FiniteBody::new(p, 0)).unwrap();
+        assert_eq!(curve.j_invariant().unwrap(), FiniteBody::new(p, 1728));
+
+        // (a, b) y (u^4 a, u^6 b) son isomorfas, tienen el mismo j
+        let u = FiniteBody::new(p, 7);
+        let curve = Curve::new(FiniteBody::new(p, 3), FiniteBody::new(p, 10)).unwrap();
+        let isomorphic = Curve::new(u.pow(4) * curve.a, u.pow(6) * curve.b).unwrap();
+        assert_eq!(curve.j_invariant(), isomorphic.j_invariant());
+    }
+}
@@ -22,14 +22,14 @@ impl AlgorithmsDLP {
         let mut res = PointEllipticCurve::new_inf(a, b);
         for b in 0..m {
             baby_steps.insert(res, b);
-            res = g * (b as usize);
+            res = g * b;
         }
 
         // giant_steps
         let mut giant_steps: HashMap<PointEllipticCurve<FiniteBody<i32>>, i32> = HashMap::new();
         let mut k = 0;
         for a in 0..m {
-            let res = (k_g + (-(g * ((a * m) as usize))).unwrap()).unwrap();
+            let res = (k_g - g * (a * m)).unwrap();
             giant_steps.insert(res, a);
             if baby_steps.contains_key(&res) {
                 k = a * m + baby_steps.get(&res).unwrap() - 1;
@@ -46,7 +46,7 @@ impl AlgorithmsDLP {
         g: PointEllipticCurve<FiniteBody<i32>>,
         k_g: PointEllipticCurve<FiniteBody<i32>>,
     ) -> i32 {
-        let mut k = 0;
+        let mut k: usize = 0;
         loop {
             k += 1;
             let res = g * k;
@@ -78,15 +78,15 @@ mod test_algorithms_dlp {
 
         println!(
             "Punto G: (x: {:?}, y: {:?})",
-            g.x.unwrap().as_value(),
-            g.y.unwrap().as_value()
+            g.x().unwrap().as_value(),
+            g.y().unwrap().as_value()
         );
         println!("y**2=x**3+905x+100");
         println!("p: {:?}", p);
         println!(
             "k * G =  (x: {:?}, y: {:?})",
-            k_g.x.unwrap().as_value(),
-            k_g.y.unwrap().as_value()
+            k_g.x().unwrap().as_value(),
+            k_g.y().unwrap().as_value()
         );
 
         let now_brute_force = Instant::now();
@@ -105,8 +105,8 @@ mod test_algorithms_dlp {
         println!("  k: {:?}", k_brute_force);
         println!(
             "  kG: (x: {:?}, y: {:?})",
-            res_brute_force.x.unwrap().as_value(),
-            res_brute_force.y.unwrap().as_value()
+            res_brute_force.x().unwrap().as_value(),
+            res_brute_force.y().unwrap().as_value()
         );
         println!("  Time elapsed: {:?} ms", time_brute_force);
 
@@ -114,8 +114,8 @@ mod test_algorithms_dlp {
         println!("  k: {:?}", k_bsgs);
         println!(
             "  kG: (x: {:?}, y: {:?})",
-            res_bsgs.x.unwrap().as_value(),
-            res_bsgs.y.unwrap().as_value()
+            res_bsgs.x().unwrap().as_value(),
+            res_bsgs.y().unwrap().as_value()
         );
         println!("  Time elapsed: {:?} ms", time_bsgs);
     }
@@ -0,0 +1,149 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::finite_body::{finite_body::FiniteBody, polynomial::Polynomial};
+
+use super::curve::{small_multiple, Curve};
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Polinomios de division f_0, f_1, ..., f_n, solo en x. Para n impar f_n = ψ_n y para n par
+    /// f_n = ψ_n / y, donde ψ_n es el polinomio de division usual: n * (x, y) = O (con (x, y) fuera
+    /// de la 2-torsion) si y solo si f_n(x) = 0. Para n impar f_n tiene grado (n^2 - 1) / 2 y
+    /// coeficiente principal n.
+    ///
+    /// Se usan las recurrencias de ψ_2m+1 y ψ_2m reemplazando y^2 por F = x^3 + ax + b:
+    ///
+    /// f_2m+1 = F^2 f_m+2 f_m^3 - f_m-1 f_m+1^3   (m par)
+    /// f_2m+1 = f_m+2 f_m^3 - F^2 f_m-1 f_m+1^3   (m impar)
+    /// f_2m = f_m (f_m+2 f_m-1^2 - f_m-2 f_m+1^2) / 2
+    pub fn division_polynomials(&self, n: usize) -> Vec<Polynomial<X>> {
+        let p = self.a.p;
+        let (zero, one) = (FiniteBody::new(p, X::zero()), FiniteBody::new(p, X::one()));
+        let constant = |k: usize| small_multiple(one, k);
+        let (a, b) = (self.a, self.b);
+
+        let curve_polynomial = Polynomial::from_coefficients(p, vec![b, a, zero, one]);
+        let f_squared = &curve_polynomial * &curve_polynomial;
+        let half = constant(2).inverse().expect("the characteristic is 2");
+
+        let mut f = vec![
+            Polynomial::zero(p),
+            Polynomial::one(p),
+            Polynomial::constant(constant(2)),
+            // 3x^4 + 6ax^2 + 12bx - a^2
+            Polynomial::from_coefficients(
+                p,
+                vec![
+                    -(a * a),
+                    constant(12) * b,
+                    constant(6) * a,
+                    zero,
+                    constant(3),
+                ],
+            ),
+            // 4(x^6 + 5ax^4 + 20bx^3 - 5a^2x^2 - 4abx - 8b^2 - a^3)
+            Polynomial::from_coefficients(
+                p,
+                vec![
+                    -(constant(8) * b * b + a * a * a),
+                    -(constant(4) * a * b),
+                    -(constant(5) * a * a),
+                    constant(20) * b,
+                    constant(5) * a,
+                    zero,
+                    one,
+                ],
+            )
+            .scale(constant(4)),
+        ];
+
+        for k in f.len()..=n {
+            let m = k / 2;
+            let next = match k % 2 {
+                1 => {
+                    let first = &f[m + 2] * &(&f[m] * &(&f[m] * &f[m]));
+                    let second = &f[m - 1] * &(&f[m + 1] * &(&f[m + 1] * &f[m + 1]));
+                    match m % 2 {
+                        0 => &(&f_squared * &first) - &second,
+                        _ => &first - &(&f_squared * &second),
+                    }
+                }
+                _ => {
+                    let first = &f[m + 2] * &(&f[m - 1] * &f[m - 1]);
+                    let second = &f[m - 2] * &(&f[m + 1] * &f[m + 1]);
+                    (&f[m] * &(&first - &second)).scale(half)
+                }
+            };
+            f.push(next);
+        }
+        f.truncate(n + 1);
+        f
+    }
+
+    /// El polinomio de division f_n de `division_polynomials`.
+    pub fn division_polynomial(&self, n: usize) -> Polynomial<X> {
+        self.division_polynomials(n).swap_remove(n)
+    }
+}
+
+#[cfg(test)]
+mod test_division_polynomial {
+    use super::*;
+
+    #[test]
+    fn test_01_degrees_and_leading_coefficients() {
+        let curve = Curve::new(FiniteBody::new(1021_i64, -3), FiniteBody::new(1021, -3)).unwrap();
+        let f = curve.division_polynomials(15);
+        assert_eq!(f.len(), 16);
+        for n in (3..=15).step_by(2) {
+            assert_eq!(f[n].degree(), Some((n * n - 1) / 2));
+            assert_eq!(
+                f[n].leading_coefficient().unwrap(),
+                FiniteBody::new(1021, n as i64)
+            );
+        }
+        // ψ_n = y f_n para n par, con grado (n^2 - 4) / 2 en x
+        for n in (4..=14).step_by(2) {
+            assert_eq!(f[n].degree(), Some((n * n - 4) / 2));
+        }
+        assert_eq!(curve.division_polynomial(7), f[7]);
+    }
+
+    #[test]
+    fn test_02_roots_are_torsion_points() {
+        // y^2 = x^3 + 6 sobre F_43 tiene 39 = 3 * 13 puntos
+        let p = 43_i64;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).unwrap();
+        let f = curve.division_polynomials(13);
+        for x in 0..p {
+            let x = FiniteBody::new(p, x);
+            let Some(y) = (x * x * x + curve.b).sqrt() else {
+                continue;
+            };
+            let point = curve.point(x, y).unwrap();
+            for n in [3_usize, 5, 7, 13] {
+                assert_eq!(
+                    f[n].evaluate(x).value == 0,
+                    point.mul_scalar(n as u64).is_identity(),
+                    "x = {:?}, n = {}",
+                    x.value,
+                    n
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{
+    curve::{one_like, small_multiple, zero_like},
+    error::EllipticCurveError,
+    named_curves::parse,
+};
+
+/// Curva de Edwards torcida: ax^2 + y^2 = 1 + dx^2y^2.
+///
+/// Si a es un cuadrado y d no lo es (ver `is_complete`), la suma de `EdwardsPoint` no tiene casos
+/// especiales: la misma formula vale para el neutro (0, 1), para P + (-P) y para P + P.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EdwardsCurve<T> {
+    pub a: T,
+    pub d: T,
+}
+
+/// Punto de una curva de Edwards torcida en coordenadas extendidas (X : Y : Z : T), que representa
+/// al punto afin (X / Z, Y / Z) con T = XY / Z. Igual que `PointEllipticCurve`, lleva consigo los
+/// coeficientes de la curva.
+///
+/// Las formulas son las de Hisil, Wong, Carter y Dawson, "Twisted Edwards Curves Revisited" (2008)
+/// (https://hyperelliptic.org/EFD/g1p/auto-twisted-extended.html).
+#[derive(Debug, Clone, Copy)]
+pub struct EdwardsPoint<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub t: T,
+    pub a: T,
+    pub d: T,
+}
+
+impl<T> EdwardsCurve<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// Curva ax^2 + y^2 = 1 + dx^2y^2. Falla si a = 0, d = 0 o a = d, los casos en que la curva es
+    /// singular.
+    pub fn new(a: T, d: T) -> Result<Self, EllipticCurveError> {
+        if a.is_zero() || d.is_zero() || a == d {
+            return Err(EllipticCurveError::SingularCurve);
+        }
+        Ok(EdwardsCurve { a, d })
+    }
+
+    /// Punto (x, y) de la curva, o None si no satisface la ecuacion.
+    pub fn point(&self, x: T, y: T) -> Option<EdwardsPoint<T>> {
+        let (x2, y2) = (x * x, y * y);
+        if self.a * x2 + y2 != one_like(x) + self.d * x2 * y2 {
+            return None;
+        }
+        Some(EdwardsPoint {
+            x,
+            y,
+            z: one_like(x),
+            t: x * y,
+            a: self.a,
+            d: self.d,
+        })
+    }
+
+    /// El neutro (0, 1).
+    pub fn identity(&self) -> EdwardsPoint<T> {
+        EdwardsPoint::identity(self.a, self.d)
+    }
+
+    pub fn contains(&self, point: &EdwardsPoint<T>) -> bool {
+        point.a == self.a && point.d == self.d
+    }
+}
+
+impl<X> EdwardsCurve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// a es un cuadrado de F_p y d no lo es: entonces el denominador de la suma nunca se anula y
+    /// la formula de `EdwardsPoint` es completa (Bernstein, Lange, "Faster addition and doubling on
+    /// elliptic curves", 2007).
+    pub fn is_complete(&self) -> bool {
+        self.a.legendre() == 1 && self.d.legendre() == -1
+    }
+}
+
+impl<T> EdwardsPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// El neutro (0 : 1 : 1 : 0).
+    pub fn identity(a: T, d: T) -> Self {
+        EdwardsPoint {
+            x: zero_like(a),
+            y: one_like(a),
+            z: one_like(a),
+            t: zero_like(a),
+            a,
+            d,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y == self.z
+    }
+
+    /// Coordenadas afines (x, y), con una inversion.
+    pub fn to_affine(&self) -> (T, T) {
+        let z_inv = one_like(self.z) / self.z;
+        (self.x * z_inv, self.y * z_inv)
+    }
+
+    /// Suma unificada "add-2008-hwcd": 9 multiplicaciones y ningun caso especial. Es completa si la
+    /// curva lo es; si no, puede fallar (Z = 0) para algunos pares de puntos.
+    pub fn add_extended(&self, other: &Self) -> Self {
+        let a = self.x * other.x;
+        let b = self.y * other.y;
+        let c = self.d * self.t * other.t;
+        let d = self.z * other.z;
+        let e = (self.x + self.y) * (other.x + other.y) - a - b;
+        let f = d - c;
+        let g = d + c;
+        let h = b - self.a * a;
+        EdwardsPoint {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+            ..*self
+        }
+    }
+
+    /// Duplicacion "dbl-2008-hwcd", sin usar T ni d.
+    pub fn double(&self) -> Self {
+        let a = self.x * self.x;
+        let b = self.y * self.y;
+        let c = small_multiple(self.z * self.z, 2);
+        let d = self.a * a;
+        let e = (self.x + self.y) * (self.x + self.y) - a - b;
+        let g = d + b;
+        let f = g - c;
+        let h = d - b;
+        EdwardsPoint {
+            x: e * f,
+            y: g * h,
+            z: f * g,
+            t: e * h,
+            ..*self
+        }
+    }
+
+    /// k * P con "double and add" de izquierda a derecha. Si k es negativo el resultado es
+    /// -(|k| * P).
+    pub fn mul_scalar<K: Into<BigInteger>>(&self, k: K) -> Self {
+        let k = k.into();
+        let magnitude = k.abs();
+
+        let mut result = Self::identity(self.a, self.d);
+        for i in (0..magnitude.bits()).rev() {
+            result = result.double();
+            if magnitude.bit(i) {
+                result = result.add_extended(self);
+            }
+        }
+        match k.is_negative() {
+            true => -result,
+            false => result,
+        }
+    }
+
+    /// Coordenada u del punto en `EdwardsCurve::to_montgomery`, u = (1 + y) / (1 - y), sin calcular
+    /// v. El neutro va al punto del infinito, que no tiene coordenada u, y (0, -1) a u = 0.
+    pub fn to_montgomery_u(&self) -> Option<T> {
+        if self.is_identity() {
+            return None;
+        }
+        Some((self.z + self.y) / (self.z - self.y))
+    }
+}
+
+impl<T> Add for EdwardsPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.add_extended(&other)
+    }
+}
+
+impl<T> Neg for EdwardsPoint<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    /// -(x, y) = (-x, y).
+    fn neg(self) -> Self::Output {
+        EdwardsPoint {
+            x: -self.x,
+            t: -self.t,
+            ..self
+        }
+    }
+}
+
+impl<T> Sub for EdwardsPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl<T> PartialEq for EdwardsPoint<T>
+where
+    T: Mul<Output = T> + PartialEq + Copy,
+{
+    /// Igualdad proyectiva: X1 Z2 = X2 Z1 y Y1 Z2 = Y2 Z1.
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a
+            && self.d == other.d
+            && self.x * other.z == other.x * self.z
+            && self.y * other.z == other.y * self.z
+    }
+}
+
+// Ed25519 (RFC 8032, 5.1): -x^2 + y^2 = 1 + dx^2y^2 sobre F_p con p = 2^255 - 19 y
+// d = -121665 / 121666
+const ED25519_D: &str = "0x52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3";
+const ED25519_BASE_X: &str = "0x216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a";
+const ED25519_BASE_Y: &str = "0x6666666666666666666666666666666666666666666666666666666666666658";
+const ED25519_ORDER: &str = "0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+
+/// La curva de Ed25519, -x^2 + y^2 = 1 - (121665 / 121666)x^2y^2 sobre F_p con p = 2^255 - 19.
+/// Es completa y birracionalmente equivalente a Curve25519.
+pub fn ed25519() -> EdwardsCurve<FiniteBody<BigInteger>> {
+    let p = (BigInteger::one() << 255) - BigInteger::from(19);
+    EdwardsCurve::new(
+        FiniteBody::new(p, -BigInteger::one()),
+        FiniteBody::new(p, parse(ED25519_D)),
+    )
+    .expect("the curve is not singular")
+}
+
+/// El punto base B de Ed25519, con y = 4 / 5 y x par. Corresponde a u = 9 en Curve25519.
+pub fn ed25519_base_point() -> EdwardsPoint<FiniteBody<BigInteger>> {
+    let curve = ed25519();
+    let p = curve.a.p;
+    curve
+        .point(
+            FiniteBody::new(p, parse(ED25519_BASE_X)),
+            FiniteBody::new(p, parse(ED25519_BASE_Y)),
+        )
+        .expect("the base point is on the curve")
+}
+
+/// El orden primo L = 2^252 + 27742317777372353535851937790883648493 de B. La curva tiene 8L
+/// puntos.
+pub fn ed25519_order() -> BigInteger {
+    parse(ED25519_ORDER)
+}
+
+#[cfg(test)]
+mod test_edwards {
+    use super::*;
+    use crate::elliptic_curve::{curve::Curve, rfc7748::curve25519};
+
+    // -x^2 + y^2 = 1 + 2x^2y^2 sobre F_101: -1 es un cuadrado y 2 no, asi que es completa
+    fn small_curve() -> EdwardsCurve<FiniteBody<i64>> {
+        EdwardsCurve::new(FiniteBody::new(101, -1), FiniteBody::new(101, 2)).unwrap()
+    }
+
+    fn points(curve: &EdwardsCurve<FiniteBody<i64>>) -> Vec<EdwardsPoint<FiniteBody<i64>>> {
+        let p = curve.a.p;
+        (0..p)
+            .flat_map(|x| (0..p).map(move |y| (x, y)))
+            .filter_map(|(x, y)| curve.point(FiniteBody::new(p, x), FiniteBody::new(p, y)))
+            .collect()
+    }
+
+    #[test]
+    fn test_01_degenerate_curves_are_rejected() {
+        let p = 101;
+        for (a, d) in [(0, 2), (-1, 0), (3, 3)] {
+            assert_eq!(
+                EdwardsCurve::new(FiniteBody::new(p, a), FiniteBody::new(p, d)),
+                Err(EllipticCurveError::SingularCurve)
+            );
+        }
+        assert!(small_curve().is_complete());
+        assert!(
+            !EdwardsCurve::new(FiniteBody::new(p, -1), FiniteBody::new(p, 4))
+                .unwrap()
+                .is_complete()
+        );
+    }
+
+    #[test]
+    fn test_02_addition_has_no_special_cases() {
+        let curve = small_curve();
+        let points = points(&curve);
+        let identity = curve.identity();
+        for point in &points {
+            assert_eq!(*point + identity, *point);
+            assert!((*point - *point).is_identity());
+            assert_eq!(*point + *point, point.double());
+        }
+        // (0, -1) tiene orden 2
+        let order_two = curve
+            .point(FiniteBody::new(101, 0), FiniteBody::new(101, -1))
+            .unwrap();
+        assert!((order_two + order_two).is_identity());
+    }
+
+    #[test]
+    fn test_03_same_group_as_weierstrass() {
+        // la suma en Edwards y en Weierstrass da el mismo resultado a traves de la equivalencia
+        let curve = small_curve();
+        assert_eq!(curve.to_montgomery().to_edwards(), curve);
+        let weierstrass: Curve<FiniteBody<i64>> = curve.to_montgomery().to_weierstrass();
+        let points = points(&curve);
+        for p in &points {
+            let p_weierstrass = p.to_weierstrass();
+            assert!(weierstrass.contains(&p_weierstrass));
+            if let Some((x, y)) = p_weierstrass.coordinates() {
+                assert!(weierstrass.point(x, y).is_some());
+            }
+            assert_eq!(curve.from_weierstrass(&p_weierstrass), Some(*p));
+            for q in &points {
+                assert_eq!(
+                    (*p + *q).to_weierstrass(),
+                    (p_weierstrass + q.to_weierstrass()).unwrap()
+                );
+            }
+        }
+        // la curva completa tiene tantos puntos como la de Weierstrass
+        assert_eq!(points.len(), weierstrass.count_points());
+    }
+
+    #[test]
+    fn test_04_scalar_multiplication() {
+        let curve = small_curve();
+        let point = points(&curve)[5];
+        let mut expected = curve.identity();
+        for k in 0..30 {
+            assert_eq!(point.mul_scalar(k), expected);
+            assert_eq!(point.mul_scalar(-k), -expected);
+            assert_eq!(
+                point.mul_scalar(k).to_weierstrass(),
+                point.to_weierstrass() * k
+            );
+            expected = expected + point;
+        }
+    }
+
+    #[test]
+    fn test_05_ed25519() {
+        let curve = ed25519();
+        let base = ed25519_base_point();
+        assert!(curve.is_complete());
+        assert!(base.mul_scalar(ed25519_order()).is_identity());
+
+        // Ed25519 y Curve25519: A = 486662 y u(B) = 9
+        let montgomery = curve.to_montgomery();
+        assert_eq!(montgomery.a, curve25519().a);
+        let nine = FiniteBody::new(curve.a.p, BigInteger::from(9));
+        assert_eq!(base.to_montgomery_u(), Some(nine));
+
+        // k * B en Edwards, en la escalera de Montgomery y en Weierstrass
+        let k = parse("0x1f2e3d4c5b6a798800112233445566778899aabbccddeeff0123456789abcdef");
+        let multiple = base.mul_scalar(k);
+        assert_eq!(
+            multiple.to_montgomery_u(),
+            Some(montgomery.ladder(k, nine, 256))
+        );
+        assert_eq!(multiple.to_weierstrass(), base.to_weierstrass() * k);
+        assert_eq!(
+            curve.from_weierstrass(&multiple.to_weierstrass()),
+            Some(multiple)
+        );
+    }
+}
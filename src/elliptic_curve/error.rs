@@ -0,0 +1,41 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EllipticCurveError {
+    /// 4a^3 + 27b^2 = 0: la cubica tiene una raiz multiple y la ley de grupo no esta definida.
+    SingularCurve,
+    /// El punto no satisface y^2 = x^3 + ax + b para la curva indicada.
+    PointNotOnCurve,
+    /// Alguna coordenada no es un elemento de [0, p - 1] del cuerpo de la curva.
+    CoordinateOutOfRange,
+    /// Se recibio el punto del infinito donde se esperaba una clave publica o un secreto.
+    PointAtInfinity,
+    /// n * Q != O: el punto no esta en el subgrupo generado por G (por ejemplo, un punto de orden chico).
+    PointNotInSubgroup,
+    /// Los bytes no tienen el formato esperado (encabezado, largo o valores fuera de rango).
+    InvalidEncoding,
+    /// La tabla de base fija es para otro generador o para otro orden.
+    InvalidFixedBaseTable,
+}
+
+impl fmt::Display for EllipticCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EllipticCurveError::SingularCurve => write!(f, "singular curve: 4a^3 + 27b^2 = 0"),
+            EllipticCurveError::PointNotOnCurve => write!(f, "the point is not on the curve"),
+            EllipticCurveError::CoordinateOutOfRange => {
+                write!(f, "a coordinate is not an element of the field")
+            }
+            EllipticCurveError::PointAtInfinity => write!(f, "unexpected point at infinity"),
+            EllipticCurveError::PointNotInSubgroup => {
+                write!(f, "the point is not in the subgroup generated by G")
+            }
+            EllipticCurveError::InvalidEncoding => write!(f, "invalid encoding"),
+            EllipticCurveError::InvalidFixedBaseTable => {
+                write!(f, "the fixed-base table is not for this generator")
+            }
+        }
+    }
+}
+
+impl Error for EllipticCurveError {}
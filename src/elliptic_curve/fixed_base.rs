@@ -0,0 +1,466 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{
+    curve::Curve, error::EllipticCurveError, jacobian_point::JacobianPoint,
+    point_elliptic_curve::PointEllipticCurve,
+};
+
+const MAGIC: &[u8; 4] = b"FBT2";
+// cada entrada empieza como en SEC1: 0x00 para O, 0x04 seguido de x e y
+const IDENTITY_TAG: u8 = 0x00;
+const AFFINE_TAG: u8 = 0x04;
+// los productos de dos elementos tienen que entrar en un BigInteger
+const MAX_INTEGER_LEN: usize = 72;
+
+/// Tabla precalculada para multiplicar siempre el mismo punto G (metodo de ventana fija).
+///
+/// Con ventana w, la fila i guarda j * 2^(w i) * G para j = 1, ..., 2^w - 1. Escribiendo
+/// k = sum k_i 2^(w i) con 0 <= k_i < 2^w, k * G = sum (k_i 2^(w i) G): una suma por ventana y
+/// ninguna duplicacion. La tabla ocupa ceil(bits(n) / w) * (2^w - 1) puntos.
+///
+/// Cada entrada se elige recorriendo toda la fila con intercambios condicionales, para que el acceso
+/// a memoria no dependa del escalar (como en la escalera de Montgomery).
+#[derive(Debug, Clone)]
+pub struct FixedBaseTable<T> {
+    window: usize,
+    order: BigInteger,
+    rows: Vec<Vec<PointEllipticCurve<T>>>,
+    a: T,
+    b: T,
+}
+
+impl<T: Copy> FixedBaseTable<T> {
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn order(&self) -> BigInteger {
+        self.order
+    }
+
+    /// El punto G de la tabla.
+    pub fn base(&self) -> PointEllipticCurve<T> {
+        self.rows[0][0]
+    }
+}
+
+impl<X> FixedBaseTable<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + From<u8>,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Tabla para `g`, cuyo orden es `order` (los escalares se reducen modulo `order`), con ventana
+    /// `window` (1 <= window <= 8).
+    pub fn new<N: Into<BigInteger>>(
+        g: &PointEllipticCurve<FiniteBody<X>>,
+        order: N,
+        window: usize,
+    ) -> Self {
+        assert!(
+            (1..=8).contains(&window),
+            "the window must be between 1 and 8"
+        );
+        let order = order.into();
+        assert!(order > BigInteger::zero(), "the order must be positive");
+        let row_count = order.bits().div_ceil(window);
+        let row_len = (1 << window) - 1;
+
+        let mut points = Vec::with_capacity(row_count * row_len);
+        let mut base = JacobianPoint::from_affine(g);
+        for _ in 0..row_count {
+            let mut acum = base;
+            for _ in 0..row_len {
+                points.push(acum);
+                acum = acum.add_jacobian(&base);
+            }
+            base = acum; // 2^w * base
+        }
+
+        let rows = JacobianPoint::batch_normalize(&points)
+            .chunks(row_len)
+            .map(|row| row.to_vec())
+            .collect();
+        FixedBaseTable {
+            window,
+            order,
+            rows,
+            a: g.a,
+            b: g.b,
+        }
+    }
+
+    /// k * G, con k reducido modulo el orden de G.
+    pub fn mul<K: Into<BigInteger>>(&self, k: K) -> PointEllipticCurve<FiniteBody<X>> {
+        let k = ((k.into() % self.order) + self.order) % self.order;
+        let p = self.a.p;
+        let identity = JacobianPoint::identity(self.a, self.b);
+
+        let mut result = identity;
+        for (i, row) in self.rows.iter().enumerate() {
+            let digit = (0..self.window).fold(0, |acc, bit| {
+                acc | (k.bit(i * self.window + bit) as usize) << bit
+            });
+            let mut selected = identity;
+            for (j, entry) in row.iter().enumerate() {
+                let mut candidate = JacobianPoint::from_affine(entry);
+                selected.conditional_swap(&mut candidate, FiniteBody::from_bit(p, j + 1 == digit));
+            }
+            result = result.add_jacobian(&selected);
+        }
+        result.to_affine()
+    }
+}
+
+impl<X> FixedBaseTable<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>
+        + TryFrom<BigInteger>,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Serializa la tabla: "FBT2", la ventana (1 byte), el largo L en bytes de p (2 bytes), p, a y b
+    /// (L bytes cada uno), el largo del orden (2 bytes), el orden, la cantidad de filas (4 bytes) y
+    /// las entradas: 0x00 si es el punto del infinito (pasa si el orden de G es chico) o 0x04 y las
+    /// coordenadas x, y (L bytes cada una). Los enteros van en big endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let p: BigInteger = self.a.p.into();
+        let field_len = p.bits().div_ceil(8);
+        let order = self.order.to_bytes_be();
+        let field = |value: X| value.into().to_bytes_be_padded(field_len);
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(self.window as u8);
+        bytes.extend_from_slice(&(field_len as u16).to_be_bytes());
+        bytes.extend(field(self.a.p));
+        bytes.extend(field(self.a.value));
+        bytes.extend(field(self.b.value));
+        bytes.extend_from_slice(&(order.len() as u16).to_be_bytes());
+        bytes.extend(order);
+        bytes.extend_from_slice(&(self.rows.len() as u32).to_be_bytes());
+        for point in self.rows.iter().flatten() {
+            match point.coordinates() {
+                None => bytes.push(IDENTITY_TAG),
+                Some((x, y)) => {
+                    bytes.push(AFFINE_TAG);
+                    bytes.extend(field(x.value));
+                    bytes.extend(field(y.value));
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Carga una tabla serializada con `to_bytes`. Se verifica el formato y que cada entrada este en
+    /// la curva, pero no que sean los multiplos correctos de G: la tabla tiene que venir de una
+    /// fuente confiable.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EllipticCurveError> {
+        let mut reader = Reader { bytes };
+        if reader.take(4)? != MAGIC {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let window = reader.take(1)?[0] as usize;
+        if !(1..=8).contains(&window) {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let field_len = reader.take_u16()?;
+        if field_len == 0 || field_len > MAX_INTEGER_LEN {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let p = BigInteger::from_bytes_be(reader.take(field_len)?);
+        let element = |value: BigInteger| -> Result<FiniteBody<X>, EllipticCurveError> {
+            if value >= p {
+                return Err(EllipticCurveError::CoordinateOutOfRange);
+            }
+            let convert =
+                |value| X::try_from(value).map_err(|_| EllipticCurveError::InvalidEncoding);
+            Ok(FiniteBody::new(convert(p)?, convert(value)?))
+        };
+
+        let a = element(BigInteger::from_bytes_be(reader.take(field_len)?))?;
+        let b = element(BigInteger::from_bytes_be(reader.take(field_len)?))?;
+        let curve = Curve::new(a, b)?;
+        let order_len = reader.take_u16()?;
+        if order_len > MAX_INTEGER_LEN {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let order = BigInteger::from_bytes_be(reader.take(order_len)?);
+        if order.is_zero() {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let row_count = reader.take_u32()?;
+        if row_count != order.bits().div_ceil(window) {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+
+        let mut rows = Vec::with_capacity(row_count);
+        for _ in 0..row_count {
+            let mut row = Vec::with_capacity((1 << window) - 1);
+            for _ in 0..(1 << window) - 1 {
+                let point = match reader.take(1)?[0] {
+                    IDENTITY_TAG => curve.identity(),
+                    AFFINE_TAG => {
+                        let x = element(BigInteger::from_bytes_be(reader.take(field_len)?))?;
+                        let y = element(BigInteger::from_bytes_be(reader.take(field_len)?))?;
+                        PointEllipticCurve::try_new(x, y, curve.a, curve.b)?
+                    }
+                    _ => return Err(EllipticCurveError::InvalidEncoding),
+                };
+                row.push(point);
+            }
+            rows.push(row);
+        }
+        if !reader.bytes.is_empty() {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+
+        Ok(FixedBaseTable {
+            window,
+            order,
+            rows,
+            a,
+            b,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], EllipticCurveError> {
+        if self.bytes.len() < len {
+            return Err(EllipticCurveError::InvalidEncoding);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn take_u16(&mut self) -> Result<usize, EllipticCurveError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+    }
+
+    fn take_u32(&mut self) -> Result<usize, EllipticCurveError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+}
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + From<u8>,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Tabla de base fija para `g`, de orden `order`. Falla si `g` no es un punto de esta curva.
+    pub fn fixed_base_table<N: Into<BigInteger>>(
+        &self,
+        g: &PointEllipticCurve<FiniteBody<X>>,
+        order: N,
+        window: usize,
+    ) -> Result<FixedBaseTable<FiniteBody<X>>, EllipticCurveError> {
+        if !self.contains(g) {
+            return Err(EllipticCurveError::PointNotOnCurve);
+        }
+        Ok(FixedBaseTable::new(g, order, window))
+    }
+}
+
+#[cfg(test)]
+mod test_fixed_base {
+    use num::Num;
+
+    use crate::elliptic_curve::{
+        named_curves::{SECP256K1, TP1_P1021, TP1_P43},
+        operation_count::count_operations,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_01_matches_scalar_multiplication() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        for window in 1..=6 {
+            let table = params
+                .curve
+                .fixed_base_table(&params.g, params.n, window)
+                .unwrap();
+            assert_eq!(table.base(), params.g);
+            for k in -50..2 * params.n {
+                assert_eq!(table.mul(k), params.g * k, "k = {}, w = {}", k, window);
+            }
+        }
+    }
+
+    #[test]
+    fn test_02_big_curve_without_doublings() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let table = FixedBaseTable::new(&params.g, params.n, 4);
+        let k = BigInteger::from_str_radix(
+            "d3b9c9d9a754ac3e9f3344d507b07fa39c6ab7104a08c720cede24428a013fda",
+            16,
+        )
+        .unwrap();
+
+        let (result, count) = count_operations(|| table.mul(k));
+        assert_eq!(result, params.g * k);
+        assert_eq!(count.doublings, 0);
+        assert!(count.additions <= 64);
+        assert_eq!(table.mul(params.n), params.curve.identity());
+    }
+
+    #[test]
+    fn test_03_point_not_on_curve() {
+        let params = TP1_P43.domain_parameters::<i32>().unwrap();
+        let other = Curve::new(FiniteBody::new(43, 0), FiniteBody::new(43, 7)).unwrap();
+        assert_eq!(
+            other.fixed_base_table(&params.g, params.n, 4).unwrap_err(),
+            EllipticCurveError::PointNotOnCurve
+        );
+    }
+
+    #[test]
+    fn test_04_serialization_round_trip() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let table = FixedBaseTable::new(&params.g, params.n, 3);
+        let bytes = table.to_bytes();
+        // encabezado + p, a, b + orden + 86 filas de 7 puntos
+        assert_eq!(bytes.len(), 4 + 1 + 2 + 3 * 32 + 2 + 32 + 4 + 86 * 7 * 65);
+
+        let loaded = FixedBaseTable::<FiniteBody<BigInteger>>::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.window(), 3);
+        assert_eq!(loaded.order(), params.n);
+        assert_eq!(loaded.base(), params.g);
+        let k = params.n - BigInteger::from(987654321);
+        assert_eq!(loaded.mul(k), params.g * k);
+
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let table = FixedBaseTable::new(&params.g, params.n, 5);
+        let loaded = FixedBaseTable::<FiniteBody<i32>>::from_bytes(&table.to_bytes()).unwrap();
+        assert_eq!(loaded.mul(655), params.g * 655);
+    }
+
+    #[test]
+    fn test_05_invalid_serialization() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let bytes = FixedBaseTable::new(&params.g, params.n, 2).to_bytes();
+        let load = FixedBaseTable::<FiniteBody<i32>>::from_bytes;
+
+        assert_eq!(
+            load(&bytes[1..]).unwrap_err(),
+            EllipticCurveError::InvalidEncoding
+        );
+        assert_eq!(
+            load(&bytes[..bytes.len() - 1]).unwrap_err(),
+            EllipticCurveError::InvalidEncoding
+        );
+
+        // cambiar la y de la ultima entrada la saca de la curva
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert_eq!(
+            load(&tampered).unwrap_err(),
+            EllipticCurveError::PointNotOnCurve
+        );
+
+        let mut huge_field = bytes.clone();
+        huge_field[5..7].copy_from_slice(&[0xff, 0xff]);
+        assert_eq!(
+            load(&huge_field).unwrap_err(),
+            EllipticCurveError::InvalidEncoding
+        );
+
+        // p no entra en un i32
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let bytes = FixedBaseTable::new(&params.g, params.n, 1).to_bytes();
+        assert_eq!(
+            load(&bytes).unwrap_err(),
+            EllipticCurveError::InvalidEncoding
+        );
+    }
+
+    #[test]
+    fn test_06_round_trip_with_identity_entries() {
+        // G de orden 7 con ventana 3: la primera fila llega a 7G = O
+        let p = 223;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 7)).unwrap();
+        let g = curve
+            .point(FiniteBody::new(p, 15), FiniteBody::new(p, 86))
+            .unwrap();
+        // G1 del TP, de orden 13, con ventana 4
+        let g1 = TP1_P43.domain_parameters::<i32>().unwrap().g;
+
+        for (g, order, window) in [(g, 7, 3), (g1, 13, 4)] {
+            let table = FixedBaseTable::new(&g, order, window);
+            assert!(table.rows.iter().flatten().any(|point| point.is_identity()));
+            let loaded = FixedBaseTable::<FiniteBody<i32>>::from_bytes(&table.to_bytes()).unwrap();
+            assert_eq!(loaded.rows, table.rows);
+            for k in 0..order {
+                assert_eq!(loaded.mul(k), g * k);
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{
+    curve::{one_like, small_multiple},
+    msm::straus,
+    point_elliptic_curve::{Coordinates, PointEllipticCurve},
+};
+
+/// Endomorfismo de GLV (Gallant-Lambert-Vanstone) de una curva y^2 = x^3 + b sobre F_p con
+/// p = 1 mod 3: φ(x, y) = (βx, y) con β una raiz cubica primitiva de la unidad en F_p. En el
+/// subgrupo de orden primo n de G, φ actua como la multiplicacion por λ, una raiz cubica primitiva
+/// de la unidad modulo n: φ(P) = λP.
+///
+/// Con una base reducida del reticulo {(x, y) : x + yλ = 0 mod n} se escribe k = k1 + k2 λ mod n con
+/// |k1|, |k2| del orden de sqrt(n), y k * P = k1 * P + k2 * φ(P) se calcula con el truco de Shamir
+/// (`straus`): la mitad de duplicaciones que `mul_scalar`, a cambio de un producto en F_p.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlvEndomorphism<T> {
+    beta: T,
+    lambda: BigInteger,
+    order: BigInteger,
+    basis: [(BigInteger, BigInteger); 2],
+    b: T,
+}
+
+impl<X> GlvEndomorphism<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Endomorfismo con los β y λ dados para el subgrupo de orden `order` generado por `g`. None si
+    /// la curva de `g` no tiene a = 0, si g = O, si β no es una raiz cubica primitiva de la unidad,
+    /// si λ^2 + λ + 1 != 0 mod n o si φ(G) != λG (β y λ no se corresponden).
+    pub fn new<L: Into<BigInteger>, N: Into<BigInteger>>(
+        g: &PointEllipticCurve<FiniteBody<X>>,
+        beta: FiniteBody<X>,
+        lambda: L,
+        order: N,
+    ) -> Option<Self> {
+        let order = order.into();
+        if order <= BigInteger::from(3) {
+            return None;
+        }
+        let lambda = ((lambda.into() % order) + order) % order;
+        let one = one_like(beta);
+        if !g.a.is_zero()
+            || g.is_identity()
+            || beta == one
+            || beta * beta * beta != one
+            || !((lambda * lambda + lambda + BigInteger::one()) % order).is_zero()
+        {
+            return None;
+        }
+
+        let glv = GlvEndomorphism {
+            beta,
+            lambda,
+            order,
+            basis: reduced_basis(order, lambda),
+            b: g.b,
+        };
+        match glv.apply(g) == g.mul_scalar(lambda) {
+            true => Some(glv),
+            false => None,
+        }
+    }
+
+    /// Busca β y λ para el subgrupo de orden `order` (primo) generado por `g`: β = (-1 + sqrt(-3)) / 2
+    /// en F_p y λ = (-1 + sqrt(-3)) / 2 modulo n, o sus conjugados β^2 = -1 - β y λ^2 = -1 - λ, y se
+    /// elige el par con φ(G) = λG. None si a != 0 o si -3 no es un cuadrado modulo p o modulo n (hace
+    /// falta p = n = 1 mod 3).
+    pub fn find<N: Into<BigInteger>>(
+        g: &PointEllipticCurve<FiniteBody<X>>,
+        order: N,
+    ) -> Option<Self> {
+        let order = order.into();
+        if order <= BigInteger::from(3) {
+            return None;
+        }
+        let p = g.b.p;
+        let three = X::one() + X::one() + X::one();
+        let one = one_like(g.b);
+        let beta = (FiniteBody::new(p, -three).sqrt()? - one) / small_multiple(one, 2);
+        let lambda_root = FiniteBody::new(order, -BigInteger::from(3)).sqrt()?;
+        let lambda = (lambda_root - FiniteBody::new(order, BigInteger::one()))
+            / FiniteBody::new(order, BigInteger::from(2));
+
+        [beta, -beta - one]
+            .into_iter()
+            .flat_map(|beta| {
+                [lambda.value, order - BigInteger::one() - lambda.value]
+                    .map(|lambda| (beta, lambda))
+            })
+            .find_map(|(beta, lambda)| Self::new(g, beta, lambda, order))
+    }
+
+    pub fn beta(&self) -> FiniteBody<X> {
+        self.beta
+    }
+
+    pub fn lambda(&self) -> BigInteger {
+        self.lambda
+    }
+
+    pub fn order(&self) -> BigInteger {
+        self.order
+    }
+
+    /// La base reducida [(a1, b1), (a2, b2)] del reticulo: a_i + b_i λ = 0 mod n.
+    pub fn basis(&self) -> [(BigInteger, BigInteger); 2] {
+        self.basis
+    }
+
+    /// φ(P) = (βx, y); el neutro queda fijo.
+    pub fn apply(
+        &self,
+        point: &PointEllipticCurve<FiniteBody<X>>,
+    ) -> PointEllipticCurve<FiniteBody<X>> {
+        match point.coordinates {
+            Coordinates::Identity => *point,
+            Coordinates::Affine { x, y } => PointEllipticCurve {
+                coordinates: Coordinates::Affine {
+                    x: self.beta * x,
+                    y,
+                },
+                a: point.a,
+                b: point.b,
+            },
+        }
+    }
+
+    /// (k1, k2) con k = k1 + k2 λ mod n (algoritmo 3.74 de "Guide to Elliptic Curve Cryptography"):
+    /// con c1 = round(b2 k / n) y c2 = round(-b1 k / n), (k1, k2) = (k, 0) - c1 (a1, b1) - c2 (a2, b2).
+    /// Como (k, 0) - (k1, k2) es el vector del reticulo mas cercano, |k1| y |k2| quedan acotados por
+    /// el largo de los vectores de la base, del orden de sqrt(n).
+    pub fn decompose<K: Into<BigInteger>>(&self, k: K) -> (BigInteger, BigInteger) {
+        let n = self.order;
+        let k = ((k.into() % n) + n) % n;
+        let [(a1, b1), (a2, b2)] = self.basis;
+        let c1 = round_div(b2 * k, n);
+        let c2 = round_div(-b1 * k, n);
+        (k - c1 * a1 - c2 * a2, -(c1 * b1) - c2 * b2)
+    }
+
+    /// k * P = k1 * P + k2 * φ(P), con ambos productos a la vez por `straus`. P tiene que estar en
+    /// el subgrupo de orden n (fuera de el φ no es la multiplicacion por λ y el resultado no es
+    /// k * P). Panics si P no esta en la curva del endomorfismo.
+    pub fn mul<K: Into<BigInteger>>(
+        &self,
+        point: &PointEllipticCurve<FiniteBody<X>>,
+        k: K,
+    ) -> PointEllipticCurve<FiniteBody<X>> {
+        assert!(
+            point.a.is_zero() && point.b == self.b,
+            "the point is not on the curve of the endomorphism"
+        );
+        let (k1, k2) = self.decompose(k);
+        straus(&[(k1, *point), (k2, self.apply(point))]).expect("both points are on the same curve")
+    }
+}
+
+// Base reducida del reticulo {(x, y) : x + yλ = 0 mod n} (algoritmo 3.74 de "Guide to Elliptic
+// Curve Cryptography"): Euclides extendido sobre n y λ da r_i = s_i n + t_i λ, es decir
+// r_i - t_i λ = 0 mod n. Con l el ultimo indice con r_l >= sqrt(n), la base es (r_{l+1}, -t_{l+1})
+// y el mas corto entre (r_l, -t_l) y (r_{l+2}, -t_{l+2}).
+fn reduced_basis(n: BigInteger, lambda: BigInteger) -> [(BigInteger, BigInteger); 2] {
+    let mut sequence = vec![(n, BigInteger::zero()), (lambda, BigInteger::one())];
+    let next = |sequence: &mut Vec<(BigInteger, BigInteger)>| {
+        let [.., (r0, t0), (r1, t1)] = sequence[..] else {
+            unreachable!("the sequence starts with two elements")
+        };
+        let q = r0 / r1;
+        sequence.push((r0 - q * r1, t0 - q * t1));
+    };
+    while sequence.last().is_some_and(|(r, _)| *r * *r >= n) {
+        next(&mut sequence);
+    }
+    next(&mut sequence);
+
+    let [.., l, l1, l2] = sequence[..] else {
+        unreachable!("the sequence has at least three elements")
+    };
+    let norm = |(r, t): (BigInteger, BigInteger)| r * r + t * t;
+    let (r, t) = match norm(l) <= norm(l2) {
+        true => l,
+        false => l2,
+    };
+    [(l1.0, -l1.1), (r, -t)]
+}
+
+// value / n redondeado al entero mas cercano, con n > 0
+fn round_div(value: BigInteger, n: BigInteger) -> BigInteger {
+    let (quotient, remainder) = (value / n, value % n);
+    match remainder.abs() * BigInteger::from(2) >= n {
+        true if value.is_negative() => quotient - BigInteger::one(),
+        true => quotient + BigInteger::one(),
+        false => quotient,
+    }
+}
+
+#[cfg(test)]
+mod test_glv {
+    use num::Num;
+    use rand::Rng;
+
+    use super::*;
+    use crate::elliptic_curve::{
+        curve::Curve, named_curves::NamedCurve, operation_count::count_operations,
+    };
+
+    type Point = PointEllipticCurve<FiniteBody<i64>>;
+
+    fn curve(p: i64) -> Curve<FiniteBody<i64>> {
+        Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 7)).unwrap()
+    }
+
+    // Un generador del subgrupo de orden `order` de y^2 = x^3 + 7 sobre F_p
+    fn generator(p: i64, order: i64) -> Point {
+        let curve = curve(p);
+        let cofactor = curve.count_points() as i64 / order;
+        curve
+            .points()
+            .map(|point| point.mul_scalar(cofactor))
+            .find(|point| !point.is_identity())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_01_find_on_small_curves() {
+        // #E = 199 sobre F_211; sobre F_223 (15, 86) tiene orden 7; #E = 4 * 7 * 151 sobre F_4099
+        let g223 = curve(223)
+            .point(FiniteBody::new(223, 15), FiniteBody::new(223, 86))
+            .unwrap();
+        for (g, order) in [
+            (generator(211, 199), 199),
+            (g223, 7),
+            (generator(4099, 151), 151),
+        ] {
+            let glv = GlvEndomorphism::find(&g, order).unwrap();
+            let beta = glv.beta();
+            assert_eq!(beta * beta * beta, one_like(beta));
+            assert_ne!(beta, one_like(beta));
+            let lambda = glv.lambda();
+            assert!(((lambda * lambda + lambda + BigInteger::one()) % order.into()).is_zero());
+
+            let mut point = g;
+            for _ in 0..order {
+                assert_eq!(glv.apply(&point), point.mul_scalar(lambda));
+                point = (point + g).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_02_decomposition() {
+        for (p, order) in [(211, 199), (4099, 151)] {
+            let glv = GlvEndomorphism::find(&generator(p, order), order).unwrap();
+            let n = BigInteger::from(order);
+            for (a, b) in glv.basis() {
+                assert!(((a + b * glv.lambda()) % n).is_zero());
+            }
+            for k in 0..order {
+                let (k1, k2) = glv.decompose(k);
+                assert!((k1 + k2 * glv.lambda() - BigInteger::from(k)) % n == BigInteger::zero());
+                // max(|k1|, |k2|) <= 2 sqrt(n)
+                for part in [k1, k2] {
+                    assert!(part * part <= BigInteger::from(4) * n, "k = {}", k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_03_mul_matches_mul_scalar() {
+        let mut rng = rand::thread_rng();
+        for (p, order) in [(211, 199), (4099, 151)] {
+            let g = generator(p, order);
+            let glv = GlvEndomorphism::find(&g, order).unwrap();
+            for _ in 0..20 {
+                let point = g.mul_scalar(rng.gen_range(1..order));
+                let k = rng.gen_range(-1000..1000);
+                assert_eq!(glv.mul(&point, k), point.mul_scalar(k), "k = {}", k);
+            }
+            assert!(glv.mul(&g, order).is_identity());
+            assert!(glv.mul(&g.mul_scalar(0), 5).is_identity());
+        }
+    }
+
+    #[test]
+    fn test_04_secp256k1() {
+        let params = NamedCurve::by_name("secp256k1")
+            .unwrap()
+            .domain_parameters::<BigInteger>()
+            .unwrap();
+        let hex = |value: &str| BigInteger::from_str_radix(value, 16).unwrap();
+        let beta = FiniteBody::new(
+            params.p,
+            hex("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee"),
+        );
+        let lambda = hex("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72");
+
+        // los valores de libsecp256k1 y la base del libro
+        let glv = GlvEndomorphism::new(&params.g, beta, lambda, params.n).unwrap();
+        let a1 = hex("3086d221a7d46bcde86c90e49284eb15");
+        assert_eq!(
+            glv.basis(),
+            [
+                (a1, -hex("e4437ed6010e88286f547fa90abfe4c3")),
+                (hex("114ca50f7a8e2f3f657c1108d9d44cfd8"), a1)
+            ]
+        );
+        let found = GlvEndomorphism::find(&params.g, params.n).unwrap();
+        assert!(found == glv || found.beta() == beta * beta);
+        assert_eq!(
+            GlvEndomorphism::new(&params.g, beta * beta, lambda, params.n),
+            None
+        );
+
+        let mut rng = rand::thread_rng();
+        let k = BigInteger::from_bytes_be(&rng.gen::<[u8; 32]>()) % params.n;
+        let (k1, k2) = glv.decompose(k);
+        assert!(k1.bits() <= 129 && k2.bits() <= 129);
+
+        let (expected, plain) = count_operations(|| params.g.mul_scalar(k));
+        let (result, accelerated) = count_operations(|| glv.mul(&params.g, k));
+        assert_eq!(result, expected);
+        assert!(accelerated.doublings * 3 < plain.doublings * 2);
+    }
+
+    #[test]
+    fn test_05_curves_without_endomorphism() {
+        // a != 0
+        let params = crate::elliptic_curve::named_curves::TP1_P1021
+            .domain_parameters::<i64>()
+            .unwrap();
+        assert_eq!(GlvEndomorphism::find(&params.g, params.n), None);
+        // p = 2 mod 3: -3 no es un cuadrado y x -> x^3 es biyectiva
+        let g = curve(1019).points().nth(1).unwrap();
+        assert_eq!(GlvEndomorphism::find(&g, 1020), None);
+        // λ que no es raiz cubica de la unidad
+        let g = generator(211, 199);
+        let glv = GlvEndomorphism::find(&g, 199).unwrap();
+        assert_eq!(GlvEndomorphism::new(&g, glv.beta(), 5, 199), None);
+        assert_eq!(
+            GlvEndomorphism::new(&g, one_like(g.b), glv.lambda(), 199),
+            None
+        );
+    }
+}
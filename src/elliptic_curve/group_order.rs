@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+use rand::Rng;
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{curve::Curve, point_elliptic_curve::PointEllipticCurve, scalar::Scalar};
+
+/// Para p > 457 el teorema de Mestre asegura que E o su twist cuadratico tiene un punto cuyo orden
+/// tiene un unico multiplo en el intervalo de Hasse. Hasta aca se cuentan los puntos uno por uno.
+const MESTRE_MIN_PRIME: u64 = 457;
+/// Hasta aca `group_order` recorre los p valores de x.
+const COUNT_POINTS_MAX_BITS: usize = 16;
+/// Hasta aca `group_order` usa el metodo de Mestre, a partir de aca el algoritmo de Schoof.
+const MESTRE_MAX_BITS: usize = 48;
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Hash
+        + Into<BigInteger>
+        + TryFrom<BigInteger>,
+{
+    /// #E(F_p), eligiendo el metodo segun el tamaño de p: `count_points` hasta 16 bits,
+    /// `order_mestre` hasta 48 bits y `order_schoof` para los primos mas grandes.
+    pub fn group_order(&self) -> BigInteger {
+        match self.a.p.into().bits() {
+            bits if bits <= COUNT_POINTS_MAX_BITS => BigInteger::from(self.count_points()),
+            bits if bits <= MESTRE_MAX_BITS => self.order_mestre(&mut rand::thread_rng()),
+            _ => self.order_schoof(),
+        }
+    }
+
+    /// #E(F_p) con el metodo de Mestre, en O(p^(1/4)) operaciones de grupo. Por el teorema de Hasse
+    /// #E esta en [p + 1 - 2 sqrt(p), p + 1 + 2 sqrt(p)], y para un punto P al azar se busca con
+    /// baby-step giant-step un m del intervalo con m * P = O. De ahi sale el orden de P, y #E es un
+    /// multiplo del minimo comun multiplo L de los ordenes encontrados.
+    ///
+    /// Si L es chico (por ejemplo si E(F_p) no es ciclico) puede haber varios multiplos en el
+    /// intervalo. Por eso se sortean tambien puntos del twist cuadratico E', que cumple
+    /// #E + #E' = 2p + 2: se termina cuando un solo N del intervalo es multiplo de L y tiene a
+    /// 2p + 2 - N multiplo de L'.
+    ///
+    /// Para p <= 457 (donde el teorema de Mestre no asegura que termine) cuenta los puntos. Panics si
+    /// p no entra en 62 bits; igual con p tan grande el metodo ya no es practico.
+    pub fn order_mestre<R: Rng + ?Sized>(&self, rng: &mut R) -> BigInteger {
+        let p = field_prime(self);
+        if p <= MESTRE_MIN_PRIME {
+            return BigInteger::from(self.count_points());
+        }
+        assert!(p < 1 << 62, "the prime is too large for Mestre's method");
+
+        // el intervalo de Hasse es simetrico respecto de p + 1, asi que vale para E y para E'
+        let bound = (4 * p).isqrt();
+        let (low, high) = (p + 1 - bound, p + 1 + bound);
+        let twist = self.quadratic_twist();
+
+        let (mut lcm_curve, mut lcm_twist) = (1, 1);
+        loop {
+            let point = self.random_point(rng);
+            lcm_curve = lcm(lcm_curve, order_in_interval(&point, low, high));
+            let point = twist.random_point(rng);
+            lcm_twist = lcm(lcm_twist, order_in_interval(&point, low, high));
+
+            let mut candidates = (low.div_ceil(lcm_curve)..=high / lcm_curve)
+                .map(|k| k * lcm_curve)
+                .filter(|n| (2 * p + 2 - n).is_multiple_of(lcm_twist));
+            if let (Some(order), None) = (candidates.next(), candidates.next()) {
+                return BigInteger::from(order);
+            }
+        }
+    }
+
+    /// Un punto afin al azar: x uniforme hasta que x^3 + ax + b sea un cuadrado, y una de sus dos
+    /// raices. Panics si la curva no tiene puntos afines.
+    pub(crate) fn random_point<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> PointEllipticCurve<FiniteBody<X>> {
+        let p = self.a.p;
+        loop {
+            let x = match X::try_from(Scalar::random(p, rng).value()) {
+                Ok(x) => FiniteBody::new(p, x),
+                Err(_) => unreachable!("x < p fits in the type of p"),
+            };
+            if let Some(y) = (x * x * x + self.a * x + self.b).sqrt() {
+                let y = if rng.gen() { y } else { -y };
+                return PointEllipticCurve::new(x, y, self.a, self.b).unwrap();
+            }
+        }
+    }
+}
+
+// p como u64
+fn field_prime<X: Copy + Into<BigInteger>>(curve: &Curve<FiniteBody<X>>) -> u64 {
+    u64::try_from(curve.a.p.into()).expect("the prime does not fit in 64 bits")
+}
+
+// Orden de P a partir de un m del intervalo [low, high] con m * P = O, que se busca con baby-step
+// giant-step: con s ~ sqrt(high - low) se guardan j * P para j < s y se recorren
+// low * P + i * (s * P) hasta encontrar -(j * P).
+fn order_in_interval<T>(point: &PointEllipticCurve<T>, low: u64, high: u64) -> u64
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Hash,
+{
+    let steps = (high - low).isqrt() + 1;
+
+    let mut baby_steps = HashMap::new();
+    let mut baby = PointEllipticCurve::new_inf(point.a, point.b);
+    for j in 0..steps {
+        baby_steps.entry(baby).or_insert(j);
+        baby = (baby + *point).unwrap();
+    }
+
+    let giant_step = point.mul_scalar(steps);
+    let mut giant = point.mul_scalar(low);
+    for i in 0..=steps {
+        if let Some(j) = baby_steps.get(&-giant) {
+            let multiple = low + i * steps + j;
+            if multiple <= high {
+                return u64::try_from(point.order_with_group_order(multiple))
+                    .expect("the order divides a u64");
+            }
+        }
+        giant = (giant + giant_step).unwrap();
+    }
+    unreachable!("#E is in the Hasse interval, so some multiple of the order is there")
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod test_group_order {
+    use super::*;
+
+    #[test]
+    fn test_01_matches_count_points() {
+        let mut rng = rand::thread_rng();
+        for (p, a, b) in [
+            (1021_i64, -3, -3),
+            (1021, 905, 100),
+            (4099, 0, 7),
+            (4099, 1, 0),
+            (65537, 2, 3),
+            (100003, 0, 5),
+        ] {
+            let curve = Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap();
+            assert_eq!(
+                curve.order_mestre(&mut rng),
+                BigInteger::from(curve.count_points()),
+                "p = {}, a = {}, b = {}",
+                p,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_02_non_cyclic_groups() {
+        // y^2 = x^3 - x sobre F_p con p = 3 mod 4 tiene los tres puntos de orden 2 y #E = p + 1: con
+        // un solo punto el orden puede quedar ambiguo, el twist lo resuelve
+        let mut rng = rand::thread_rng();
+        for p in [1019_i64, 4099, 10007] {
+            let curve = Curve::new(FiniteBody::new(p, -1), FiniteBody::new(p, 0)).unwrap();
+            assert_eq!(curve.order_mestre(&mut rng), BigInteger::from(p + 1));
+        }
+    }
+
+    #[test]
+    fn test_03_small_primes() {
+        let mut rng = rand::thread_rng();
+        let curve = Curve::new(FiniteBody::new(43, 0), FiniteBody::new(43, 6)).unwrap();
+        assert_eq!(curve.order_mestre(&mut rng), BigInteger::from(39));
+        assert_eq!(curve.group_order(), BigInteger::from(39));
+    }
+
+    #[test]
+    fn test_04_large_prime() {
+        // p = 2^40 + 15: contar los puntos uno por uno tomaria 2^40 simbolos de Legendre
+        let mut rng = rand::thread_rng();
+        let p: i128 = (1 << 40) + 15;
+        let curve = Curve::new(FiniteBody::new(p, 3), FiniteBody::new(p, 11)).unwrap();
+        let order = curve.group_order();
+        let order_value = i128::try_from(order).unwrap();
+        assert!((p + 1 - order_value).pow(2) <= 4 * p);
+
+        // el orden anula a todos los puntos de E y 2p + 2 - #E a los del twist
+        let twist = curve.quadratic_twist();
+        for _ in 0..10 {
+            assert!(curve.random_point(&mut rng).mul_scalar(order).is_identity());
+            assert!(twist
+                .random_point(&mut rng)
+                .mul_scalar(2 * p + 2 - order_value)
+                .is_identity());
+        }
+        assert_eq!(curve.order_mestre(&mut rng), order);
+    }
+}
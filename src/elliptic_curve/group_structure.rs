@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{
+    big_integer::BigInteger,
+    factorization::{divisors, factor},
+    finite_body::finite_body::FiniteBody,
+};
+
+use super::{curve::Curve, point_elliptic_curve::PointEllipticCurve};
+
+/// Estructura de E(F_p) como grupo abstracto: E ≅ Z/n1 x Z/n2 con n1 | n2, junto con puntos g1 y g2
+/// de ordenes n1 y n2 tales que E = <g1> ⊕ <g2>. El subgrupo ciclico mas grande es <g2>; la curva es
+/// ciclica si n1 = 1.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupStructure<T> {
+    pub n1: BigInteger,
+    pub n2: BigInteger,
+    pub g1: PointEllipticCurve<T>,
+    pub g2: PointEllipticCurve<T>,
+}
+
+impl<T> GroupStructure<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// #E = n1 * n2.
+    pub fn order(&self) -> BigInteger {
+        self.n1 * self.n2
+    }
+
+    pub fn is_cyclic(&self) -> bool {
+        self.n1.is_one()
+    }
+
+    /// Generador del subgrupo ciclico mas grande, de orden n2.
+    pub fn generator(&self) -> PointEllipticCurve<T> {
+        self.g2
+    }
+
+    /// Ordenes de los subgrupos de E: todos los divisores de #E, porque un grupo abeliano finito
+    /// tiene subgrupos de cada orden que divide al suyo.
+    pub fn subgroup_orders(&self) -> Vec<BigInteger> {
+        divisors(self.order())
+    }
+
+    /// Ordenes posibles de un punto, que son los ordenes de los subgrupos ciclicos: los divisores
+    /// de n2.
+    pub fn point_orders(&self) -> Vec<BigInteger> {
+        divisors(self.n2)
+    }
+
+    /// Un punto de orden d, (n2 / d) * g2, o None si no hay puntos de ese orden (d no divide a n2).
+    pub fn point_of_order<N: Into<BigInteger>>(&self, d: N) -> Option<PointEllipticCurve<T>> {
+        let d = d.into();
+        if d <= BigInteger::zero() || !(self.n2 % d).is_zero() {
+            return None;
+        }
+        Some(self.g2.mul_scalar(self.n2 / d))
+    }
+}
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Hash
+        + Into<BigInteger>
+        + TryFrom<BigInteger>,
+{
+    /// Estructura de E(F_p) conociendo #E. Se trabaja con cada parte q-primaria H (los puntos de
+    /// orden q^k, con q^e || #E), que es Z/q^b x Z/q^c con b + c = e: con puntos al azar de H se
+    /// busca uno P de orden maximo y otro Q con <P> ∩ <Q> = {O} hasta que |<P> ⊕ <Q>| = q^e.
+    /// Sumando los P y los Q de cada primo salen g2 y g1.
+    ///
+    /// Para reducir Q modulo <P> hace logaritmos discretos en el subgrupo de orden q, solo para los
+    /// primos con q^2 | #E. Panics si `group_order` no es #E o si uno de esos primos no entra en 64
+    /// bits.
+    pub fn group_structure<N: Into<BigInteger>>(
+        &self,
+        group_order: N,
+    ) -> GroupStructure<FiniteBody<X>> {
+        let group_order = group_order.into();
+        let mut rng = rand::thread_rng();
+        let identity = self.identity();
+        let (mut n1, mut n2) = (BigInteger::one(), BigInteger::one());
+        let (mut g1, mut g2) = (identity, identity);
+
+        for (prime, exponent) in factor(group_order) {
+            let mut primary_order = BigInteger::one();
+            for _ in 0..exponent {
+                primary_order = primary_order * prime;
+            }
+            let cofactor = group_order / primary_order;
+
+            // P de orden q^b y Q de orden q^c, con <P> ∩ <Q> = {O}
+            let (mut p_point, mut b) = (identity, 0);
+            let (mut q_point, mut c) = (identity, 0);
+            while b + c < exponent {
+                let point = self.random_point(&mut rng).mul_scalar(cofactor);
+                let k = order_exponent(&point, prime, exponent);
+                if k > b {
+                    (p_point, b) = (point, k);
+                    (q_point, c) = (identity, 0);
+                    continue;
+                }
+
+                // el menor j con q^j * R en <P>: como P tiene orden maximo, q^j divide al logaritmo
+                // y R - (y / q^j) * P tiene orden q^j
+                let mut j = 0;
+                let mut multiple = point;
+                let log = loop {
+                    if let Some(log) = log_in_cyclic(&p_point, prime, b, &multiple) {
+                        break log;
+                    }
+                    multiple = multiple.mul_scalar(prime);
+                    j += 1;
+                };
+                if j > c {
+                    let mut power = BigInteger::one();
+                    for _ in 0..j {
+                        power = power * prime;
+                    }
+                    q_point = (point - p_point.mul_scalar(log / power)).unwrap();
+                    c = j;
+                }
+            }
+            if c > b {
+                (p_point, q_point) = (q_point, p_point);
+                (b, c) = (c, b);
+            }
+
+            for _ in 0..b {
+                n2 = n2 * prime;
+            }
+            for _ in 0..c {
+                n1 = n1 * prime;
+            }
+            g2 = (g2 + p_point).unwrap();
+            g1 = (g1 + q_point).unwrap();
+        }
+
+        GroupStructure { n1, n2, g1, g2 }
+    }
+
+    /// `group_structure` calculando antes #E con `group_order`.
+    pub fn compute_group_structure(&self) -> GroupStructure<FiniteBody<X>> {
+        self.group_structure(self.group_order())
+    }
+}
+
+// k tal que el orden de P es q^k, sabiendo que q^e * P = O
+fn order_exponent<T>(point: &PointEllipticCurve<T>, prime: BigInteger, exponent: u32) -> u32
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    let mut multiple = *point;
+    for k in 0..exponent {
+        if multiple.is_identity() {
+            return k;
+        }
+        multiple = multiple.mul_scalar(prime);
+    }
+    assert!(multiple.is_identity(), "the group order is not #E");
+    exponent
+}
+
+// y con R = y * P, o None si R no esta en <P>, para P de orden q^b. Se calcula y digito a digito en
+// base q: el digito i sale del logaritmo de q^(b-1-i) * (R - y * P) en el subgrupo de orden q.
+fn log_in_cyclic<T>(
+    point: &PointEllipticCurve<T>,
+    prime: BigInteger,
+    exponent: u32,
+    target: &PointEllipticCurve<T>,
+) -> Option<BigInteger>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Hash,
+{
+    let multiply_by_prime = |point: PointEllipticCurve<T>, times: u32| {
+        (0..times).fold(point, |point, _| point.mul_scalar(prime))
+    };
+    let base = multiply_by_prime(*point, exponent.saturating_sub(1));
+
+    let mut log = BigInteger::zero();
+    let mut power = BigInteger::one();
+    for i in 0..exponent {
+        let remainder = (*target - point.mul_scalar(log)).unwrap();
+        let digit = log_order_prime(
+            &base,
+            prime,
+            &multiply_by_prime(remainder, exponent - 1 - i),
+        )?;
+        log = log + power * digit;
+        power = power * prime;
+    }
+    (point.mul_scalar(log) == *target).then_some(log)
+}
+
+// Logaritmo de R en base P de orden primo q con baby-step giant-step, o None si R no esta en <P>
+fn log_order_prime<T>(
+    point: &PointEllipticCurve<T>,
+    prime: BigInteger,
+    target: &PointEllipticCurve<T>,
+) -> Option<BigInteger>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Hash,
+{
+    let prime = u64::try_from(prime).expect("the prime factor does not fit in 64 bits");
+    let steps = prime.isqrt() + 1;
+
+    let mut baby_steps = HashMap::new();
+    let mut baby = PointEllipticCurve::new_inf(point.a, point.b);
+    for j in 0..steps {
+        baby_steps.entry(baby).or_insert(j);
+        baby = (baby + *point).unwrap();
+    }
+
+    let giant_step = -point.mul_scalar(steps);
+    let mut giant = *target;
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&giant) {
+            return Some(BigInteger::from(i * steps + j));
+        }
+        giant = (giant + giant_step).unwrap();
+    }
+    None
+}
+
+#[cfg(test)]
+mod test_group_structure {
+    use crate::elliptic_curve::named_curves::{SECP256K1, SECP256R1};
+
+    use super::*;
+
+    fn big(value: u64) -> BigInteger {
+        BigInteger::from(value)
+    }
+
+    fn check_structure(p: i64, a: i64, b: i64, n1: u64, n2: u64) {
+        let curve = Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap();
+        let structure = curve.compute_group_structure();
+        assert_eq!(
+            (structure.n1, structure.n2),
+            (big(n1), big(n2)),
+            "p = {}",
+            p
+        );
+        assert_eq!(structure.g1.order_with_group_order(n1), big(n1));
+        assert_eq!(structure.g2.order_with_group_order(n2), big(n2));
+
+        // <g1> ∩ <g2> = {O}: los n1 * n2 puntos i * g1 + j * g2 son distintos
+        let mut points = std::collections::HashSet::new();
+        for i in 0..n1 {
+            for j in 0..n2 {
+                points.insert((structure.g1 * i + structure.g2 * j).unwrap());
+            }
+        }
+        assert_eq!(points.len() as u64, n1 * n2);
+    }
+
+    #[test]
+    fn test_01_ejercicio3() {
+        // y^2 = x^3 + 6 sobre F_43 es ciclica de orden 39: G2 = (9, 2) la genera y G1 = (13, 15)
+        // solo genera el subgrupo de orden 13
+        let p = 43_i64;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).unwrap();
+        let structure = curve.compute_group_structure();
+        assert!(structure.is_cyclic());
+        assert_eq!(structure.n2, big(39));
+        assert_eq!(
+            structure.generator().order_with_group_order(39_u64),
+            big(39)
+        );
+        assert_eq!(
+            structure.point_orders(),
+            vec![big(1), big(3), big(13), big(39)]
+        );
+
+        let g1 = curve.point(FiniteBody::new(p, 13), FiniteBody::new(p, 15));
+        let g2 = curve.point(FiniteBody::new(p, 9), FiniteBody::new(p, 2));
+        assert_eq!(
+            g1.unwrap().order_with_group_order(structure.order()),
+            big(13)
+        );
+        assert_eq!(
+            g2.unwrap().order_with_group_order(structure.order()),
+            structure.n2
+        );
+    }
+
+    #[test]
+    fn test_02_non_cyclic_groups() {
+        check_structure(1019, -1, 0, 2, 510);
+        check_structure(73, 0, 1, 2, 42);
+        check_structure(61, 0, 1, 4, 12);
+        check_structure(31, 0, 1, 6, 6);
+    }
+
+    #[test]
+    fn test_03_points_of_every_order() {
+        let p = 61_i64;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 1)).unwrap();
+        let structure = curve.compute_group_structure();
+        assert_eq!(structure.subgroup_orders().len(), 10); // divisores de 48
+        for d in 1..=13_u64 {
+            let point = structure.point_of_order(d);
+            match 12 % d {
+                0 => assert_eq!(point.unwrap().order_with_group_order(48_u64), big(d)),
+                _ => assert!(point.is_none(), "d = {}", d),
+            }
+        }
+    }
+
+    #[test]
+    fn test_04_named_curves() {
+        for named_curve in [SECP256K1, SECP256R1] {
+            let params = named_curve.domain_parameters::<BigInteger>().unwrap();
+            let structure = params.curve.group_structure(params.n * params.h);
+            assert!(structure.is_cyclic());
+            assert_eq!(structure.n2, params.n);
+            assert!(structure.g2.mul_scalar(params.n).is_identity());
+        }
+    }
+}
@@ -0,0 +1,847 @@
+use num::{One, Zero};
+
+use crate::{
+    big_integer::BigInteger,
+    finite_body::{finite_body::FiniteBody, polynomial::Polynomial},
+    sha256::{sha256, BLOCK_SIZE, OUTPUT_SIZE},
+};
+
+use super::{
+    curve::Curve,
+    named_curves::{parse, NamedCurve, SECP256K1, SECP256R1},
+    point_elliptic_curve::PointEllipticCurve,
+};
+
+/// Nivel de seguridad k de los suites, en bits: cada elemento de hash_to_field sale de
+/// ceil(log2(p)) + k bits uniformes, asi el sesgo de reducir modulo p es de 2^-k.
+const SECURITY_BITS: usize = 128;
+/// Los DST mas largos se reemplazan por H("H2C-OVERSIZE-DST-" || DST) (RFC 9380, 5.3.3).
+const MAX_DST_LENGTH: usize = 255;
+/// Intentos de `map_to_curve_try_and_increment` en `Curve::hash_to_curve`: cada uno falla con
+/// probabilidad cercana a 1/2, asi que no encontrar un punto tiene probabilidad ~2^-256.
+const TRY_AND_INCREMENT_ATTEMPTS: u32 = 256;
+
+/// Isogenia E' -> E para las curvas con a * b = 0, donde no se puede usar el mapa SSWU: se aplica el
+/// mapa en E': y^2 = x^3 + a' x + b' y despues
+/// (x, y) -> (x_num(x) / x_den(x), y * y_num(x) / y_den(x)). Los coeficientes van de menor a mayor
+/// grado.
+#[derive(Debug)]
+pub struct Isogeny {
+    a: &'static str,
+    b: &'static str,
+    x_numerator: &'static [&'static str],
+    x_denominator: &'static [&'static str],
+    y_numerator: &'static [&'static str],
+    y_denominator: &'static [&'static str],
+}
+
+/// Suite de RFC 9380 con expand_message_xmd sobre SHA-256 y el mapa SSWU simplificado: la curva, la
+/// constante Z del mapa (un no cuadrado de F_p), la isogenia si hace falta y el cofactor h_eff.
+/// `hash_to_curve` es el suite "_RO_" y `encode_to_curve` el "_NU_". Para otras curvas esta
+/// `Curve::hash_to_curve`.
+#[derive(Debug)]
+pub struct HashToCurveSuite {
+    pub name: &'static str,
+    curve: &'static NamedCurve,
+    z: i64,
+    isogeny: Option<Isogeny>,
+    h_eff: &'static str,
+}
+
+/// P256_XMD:SHA-256_SSWU_RO_ / _NU_ (RFC 9380, 8.2).
+pub const P256_XMD_SHA256_SSWU: HashToCurveSuite = HashToCurveSuite {
+    name: "P256_XMD:SHA-256_SSWU",
+    curve: &SECP256R1,
+    z: -10,
+    isogeny: None,
+    h_eff: "0x1",
+};
+
+/// secp256k1_XMD:SHA-256_SSWU_RO_ / _NU_ (RFC 9380, 8.7), con la 3-isogenia del apendice E.1.
+pub const SECP256K1_XMD_SHA256_SSWU: HashToCurveSuite = HashToCurveSuite {
+    name: "secp256k1_XMD:SHA-256_SSWU",
+    curve: &SECP256K1,
+    z: -11,
+    isogeny: Some(Isogeny {
+        a: "0x3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533",
+        b: "0x6eb",
+        x_numerator: &[
+            "0x8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+            "0x7d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+            "0x534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+            "0x8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+        ],
+        x_denominator: &[
+            "0xd35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+            "0xedadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+            "0x1",
+        ],
+        y_numerator: &[
+            "0x4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+            "0xc75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+            "0x29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+            "0x2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+        ],
+        y_denominator: &[
+            "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+            "0x7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+            "0x6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+            "0x1",
+        ],
+    }),
+    h_eff: "0x1",
+};
+
+impl HashToCurveSuite {
+    /// hash_to_curve (RFC 9380, 3): un punto de la curva con distribucion indistinguible de la
+    /// uniforme y del que no se conoce el logaritmo discreto. Se mapean dos elementos de
+    /// hash_to_field, se suman y se limpia el cofactor. `dst` separa los usos de distintas
+    /// aplicaciones.
+    pub fn hash_to_curve(
+        &self,
+        msg: &[u8],
+        dst: &[u8],
+    ) -> PointEllipticCurve<FiniteBody<BigInteger>> {
+        let u = hash_to_field(msg, dst, self.p(), 2);
+        let q0 = self.map_to_curve(u[0]);
+        let q1 = self.map_to_curve(u[1]);
+        self.clear_cofactor((q0 + q1).unwrap())
+    }
+
+    /// encode_to_curve (RFC 9380, 3): como `hash_to_curve` pero con un solo elemento de
+    /// hash_to_field. Es la mitad de trabajo, pero el resultado no es uniforme: solo cubre una
+    /// fraccion de los puntos.
+    pub fn encode_to_curve(
+        &self,
+        msg: &[u8],
+        dst: &[u8],
+    ) -> PointEllipticCurve<FiniteBody<BigInteger>> {
+        let u = hash_to_field(msg, dst, self.p(), 1);
+        self.clear_cofactor(self.map_to_curve(u[0]))
+    }
+
+    /// Mapa de un elemento de F_p a un punto de la curva: SSWU simplificado, sobre E' y con la
+    /// isogenia si el suite tiene una.
+    pub fn map_to_curve(
+        &self,
+        u: FiniteBody<BigInteger>,
+    ) -> PointEllipticCurve<FiniteBody<BigInteger>> {
+        let params = self.curve.domain_parameters::<BigInteger>().unwrap();
+        let p = params.p;
+        let z = FiniteBody::new(p, BigInteger::from(self.z));
+        let (a, b) = (params.curve.a, params.curve.b);
+
+        let Some(isogeny) = &self.isogeny else {
+            let (x, y) = map_to_curve_simple_swu(u, a, b, z);
+            return params.curve.point(x, y).expect("SSWU maps to the curve");
+        };
+
+        let (a_isogenous, b_isogenous) = (
+            FiniteBody::new(p, parse(isogeny.a)),
+            FiniteBody::new(p, parse(isogeny.b)),
+        );
+        let (x, y) = map_to_curve_simple_swu(u, a_isogenous, b_isogenous, z);
+        let evaluate = |coefficients: &[&str]| {
+            let coefficients: Vec<BigInteger> = coefficients.iter().map(|c| parse(c)).collect();
+            Polynomial::new(p, &coefficients).evaluate(x)
+        };
+        let x_denominator = evaluate(isogeny.x_denominator);
+        let y_denominator = evaluate(isogeny.y_denominator);
+        // los ceros de los denominadores son los puntos del nucleo, que van a O
+        let (Some(x_inverse), Some(y_inverse)) = (x_denominator.inverse(), y_denominator.inverse())
+        else {
+            return params.curve.identity();
+        };
+        let x_mapped = evaluate(isogeny.x_numerator) * x_inverse;
+        let y_mapped = y * evaluate(isogeny.y_numerator) * y_inverse;
+        params
+            .curve
+            .point(x_mapped, y_mapped)
+            .expect("the isogeny maps to the curve")
+    }
+
+    /// h_eff * P, que esta en el subgrupo de orden primo.
+    pub fn clear_cofactor(
+        &self,
+        point: PointEllipticCurve<FiniteBody<BigInteger>>,
+    ) -> PointEllipticCurve<FiniteBody<BigInteger>> {
+        point.mul_scalar(parse(self.h_eff))
+    }
+
+    fn p(&self) -> BigInteger {
+        self.curve.domain_parameters::<BigInteger>().unwrap().p
+    }
+}
+
+/// expand_message_xmd con SHA-256 (RFC 9380, 5.3.1): `len_in_bytes` bytes pseudoaleatorios a partir
+/// de `msg` y `dst`, encadenando hashes b_1, b_2, ... que dependen de b_0 = H(Z_pad || msg || ...).
+/// Panics si `len_in_bytes` es 0, pasa de 65535 o necesita mas de 255 bloques de 32 bytes.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    let ell = len_in_bytes.div_ceil(OUTPUT_SIZE);
+    assert!(
+        len_in_bytes > 0 && len_in_bytes <= u16::MAX as usize && ell <= 255,
+        "invalid length for expand_message_xmd"
+    );
+
+    let oversize;
+    let dst = match dst.len() > MAX_DST_LENGTH {
+        true => {
+            oversize = sha256(&[b"H2C-OVERSIZE-DST-".as_slice(), dst].concat());
+            oversize.as_slice()
+        }
+        false => dst,
+    };
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let msg_prime = [
+        &[0; BLOCK_SIZE],
+        msg,
+        &(len_in_bytes as u16).to_be_bytes(),
+        &[0],
+        &dst_prime,
+    ]
+    .concat();
+    let b_0 = sha256(&msg_prime);
+
+    let mut uniform_bytes = Vec::with_capacity(ell * OUTPUT_SIZE);
+    let mut b_i = sha256(&[b_0.as_slice(), &[1], &dst_prime].concat());
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mixed: Vec<u8> = b_0.iter().zip(b_i).map(|(x, y)| x ^ y).collect();
+        b_i = sha256(&[mixed.as_slice(), &[i as u8], &dst_prime].concat());
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// hash_to_field (RFC 9380, 5.2): `count` elementos de F_p, cada uno de L = ceil((ceil(log2(p)) +
+/// k) / 8) bytes de expand_message_xmd reducidos modulo p.
+pub fn hash_to_field(
+    msg: &[u8],
+    dst: &[u8],
+    p: BigInteger,
+    count: usize,
+) -> Vec<FiniteBody<BigInteger>> {
+    let length = (p.bits() + SECURITY_BITS).div_ceil(8);
+    expand_message_xmd(msg, dst, count * length)
+        .chunks_exact(length)
+        .map(|bytes| FiniteBody::new(p, BigInteger::from_bytes_be(bytes)))
+        .collect()
+}
+
+/// Mapa SSWU simplificado (RFC 9380, 6.6.2) para y^2 = x^3 + ax + b con a * b != 0 y Z un no
+/// cuadrado: x1 = (-b / a) (1 + 1 / (Z^2 u^4 + Z u^2)), y si g(x1) no es un cuadrado lo es
+/// g(Z u^2 x1). El signo de y es el de u.
+pub fn map_to_curve_simple_swu(
+    u: FiniteBody<BigInteger>,
+    a: FiniteBody<BigInteger>,
+    b: FiniteBody<BigInteger>,
+    z: FiniteBody<BigInteger>,
+) -> (FiniteBody<BigInteger>, FiniteBody<BigInteger>) {
+    let one = FiniteBody::new(u.p, BigInteger::one());
+    let g = |x: FiniteBody<BigInteger>| x * x * x + a * x + b;
+    let inverse = |value: FiniteBody<BigInteger>| value.inverse().expect("a * b != 0");
+
+    let z_u2 = z * u * u;
+    let tv1 = z_u2 * z_u2 + z_u2;
+    let x1 = match tv1.value.is_zero() {
+        // caso excepcional: u = 0 o Z u^2 = -1
+        true => b * inverse(z * a),
+        false => -b * inverse(a) * (one + inverse(tv1)),
+    };
+    let (x, y) = match g(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = z_u2 * x1;
+            let y = g(x2).sqrt().expect("g(x1) or g(x2) is a square");
+            (x2, y)
+        }
+    };
+    match sgn0(u) == sgn0(y) {
+        true => (x, y),
+        false => (x, -y),
+    }
+}
+
+/// La constante Z del mapa SSWU para y^2 = x^3 + ax + b con a * b != 0 (RFC 9380, apendice H.2):
+/// el primer candidato 1, -1, 2, -2, ... que no es un cuadrado, no es -1, deja a g(x) - Z sin
+/// raices y hace de g(b / (Z a)) un cuadrado.
+pub fn find_z_sswu(a: FiniteBody<BigInteger>, b: FiniteBody<BigInteger>) -> FiniteBody<BigInteger> {
+    let p = a.p;
+    let g = |x: FiniteBody<BigInteger>| x * x * x + a * x + b;
+    let minus_one = FiniteBody::new(p, -BigInteger::one());
+    (1..)
+        .flat_map(|i: i64| [i, -i])
+        .map(|candidate| FiniteBody::new(p, BigInteger::from(candidate)))
+        .find(|&z| {
+            if z.legendre() != -1 || z == minus_one {
+                return false;
+            }
+            let (zero, one) = (BigInteger::zero(), BigInteger::one());
+            let g_minus_z = Polynomial::new(p, &[(b - z).value, a.value, zero, one]);
+            // g(x) - Z no tiene raices si y solo si gcd(x^p - x, g(x) - Z) = 1
+            let x = Polynomial::x(p);
+            let split = (&x.pow_mod(p, &g_minus_z) - &x).gcd(&g_minus_z);
+            split.degree() == Some(0)
+                && (z * a)
+                    .inverse()
+                    .is_some_and(|inverse| g(b * inverse).legendre() == 1)
+        })
+        .expect("some small integer is a valid Z")
+}
+
+/// Mapa "try-and-increment": para i = 0, 1, ... se toma x de hash_to_field(msg || i) hasta que
+/// x^3 + ax + b es un cuadrado, y el signo de y sale de un segundo elemento del mismo hash. Sirve
+/// para cualquier curva, tambien con a * b = 0, pero la cantidad de intentos depende del mensaje,
+/// asi que no es de tiempo constante. Devuelve None si ningun x de los primeros `max_attempts` es
+/// la abscisa de un punto.
+pub fn map_to_curve_try_and_increment(
+    curve: &Curve<FiniteBody<BigInteger>>,
+    msg: &[u8],
+    dst: &[u8],
+    max_attempts: u32,
+) -> Option<PointEllipticCurve<FiniteBody<BigInteger>>> {
+    let (a, b) = (curve.a, curve.b);
+    (0..max_attempts).find_map(|i| {
+        let u = hash_to_field(&[msg, &i.to_be_bytes()].concat(), dst, a.p, 2);
+        let x = u[0];
+        let y = (x * x * x + a * x + b).sqrt()?;
+        let y = match sgn0(u[1]) == sgn0(y) {
+            true => y,
+            false => -y,
+        };
+        Some(curve.point(x, y).expect("y^2 = x^3 + ax + b"))
+    })
+}
+
+impl Curve<FiniteBody<BigInteger>> {
+    /// hash_to_curve para una curva cualquiera, sin un suite de RFC 9380: si a * b != 0 se usa el
+    /// mapa SSWU con Z de `find_z_sswu` sobre dos elementos de hash_to_field, y si no hay una
+    /// isogenia a mano, `map_to_curve_try_and_increment`. El resultado se multiplica por
+    /// `cofactor` para caer en el subgrupo de orden primo. Devuelve None solo si falla el
+    /// try-and-increment.
+    pub fn hash_to_curve(
+        &self,
+        msg: &[u8],
+        dst: &[u8],
+        cofactor: BigInteger,
+    ) -> Option<PointEllipticCurve<FiniteBody<BigInteger>>> {
+        let (a, b) = (self.a, self.b);
+        let point = match a.value.is_zero() || b.value.is_zero() {
+            true => map_to_curve_try_and_increment(self, msg, dst, TRY_AND_INCREMENT_ATTEMPTS)?,
+            false => {
+                let z = find_z_sswu(a, b);
+                let map = |u| {
+                    let (x, y) = map_to_curve_simple_swu(u, a, b, z);
+                    self.point(x, y).expect("SSWU maps to the curve")
+                };
+                let u = hash_to_field(msg, dst, a.p, 2);
+                (map(u[0]) + map(u[1])).expect("the sum of two points is on the curve")
+            }
+        };
+        Some(point.mul_scalar(cofactor))
+    }
+}
+
+// Signo de un elemento de F_p (RFC 9380, 4.1): la paridad de su representante en [0, p)
+fn sgn0(value: FiniteBody<BigInteger>) -> bool {
+    value.value.is_odd()
+}
+
+#[cfg(test)]
+mod test_hash_to_curve {
+    use num::Num;
+
+    use crate::elliptic_curve::named_curves::TP1_P223;
+
+    use super::*;
+
+    struct RfcVector {
+        u: [&'static str; 2],
+        q0: (&'static str, &'static str),
+        q1: (&'static str, &'static str),
+        p: (&'static str, &'static str),
+    }
+
+    // encode_to_curve: un solo u y P = Q0
+    struct RfcNuVector {
+        u: &'static str,
+        p: (&'static str, &'static str),
+    }
+
+    // Mensajes de los vectores de RFC 9380 (apendices J y K), en el mismo orden que los vectores
+    fn rfc_messages() -> Vec<Vec<u8>> {
+        vec![
+            b"".to_vec(),
+            b"abc".to_vec(),
+            b"abcdef0123456789".to_vec(),
+            [b"q128_".as_slice(), &[b'q'; 128]].concat(),
+            [b"a512_".as_slice(), &[b'a'; 512]].concat(),
+        ]
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn field(p: BigInteger, hex: &str) -> FiniteBody<BigInteger> {
+        FiniteBody::new(p, BigInteger::from_str_radix(hex, 16).unwrap())
+    }
+
+    fn assert_vectors(suite: &HashToCurveSuite, dst: &[u8], vectors: &[RfcVector]) {
+        let params = suite.curve.domain_parameters::<BigInteger>().unwrap();
+        let point = |(x, y): (&str, &str)| {
+            params
+                .curve
+                .point(field(params.p, x), field(params.p, y))
+                .unwrap()
+        };
+
+        for (msg, vector) in rfc_messages().iter().zip(vectors) {
+            let u = hash_to_field(msg, dst, params.p, 2);
+            assert_eq!(
+                u,
+                vec![field(params.p, vector.u[0]), field(params.p, vector.u[1])]
+            );
+            assert_eq!(suite.map_to_curve(u[0]), point(vector.q0));
+            assert_eq!(suite.map_to_curve(u[1]), point(vector.q1));
+            assert_eq!(suite.hash_to_curve(msg, dst), point(vector.p));
+        }
+    }
+
+    #[test]
+    fn test_01_expand_message_xmd() {
+        // RFC 9380, apendice K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            expand_message_xmd(b"", dst, 0x20),
+            from_hex("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+        );
+        assert_eq!(
+            expand_message_xmd(b"abc", dst, 0x20),
+            from_hex("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+        );
+        assert_eq!(
+            expand_message_xmd(&rfc_messages()[4], dst, 0x20),
+            from_hex("4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c")
+        );
+        assert_eq!(
+            expand_message_xmd(b"", dst, 0x80),
+            from_hex(
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd\
+                 4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b97\
+                 9d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b64\
+                 6c993f0ced"
+            )
+        );
+
+        // DST de mas de 255 bytes
+        let long_dst = [
+            b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-".as_slice(),
+            &[b'1'; 208],
+        ]
+        .concat();
+        assert_eq!(
+            expand_message_xmd(b"", &long_dst, 0x20),
+            from_hex("e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3")
+        );
+        assert_eq!(
+            expand_message_xmd(b"abc", &long_dst, 0x20),
+            from_hex("52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12")
+        );
+    }
+
+    // RFC 9380, apendice J.1.1
+    const P256_VECTORS: [RfcVector; 5] = [
+        RfcVector {
+            u: [
+                "ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009",
+                "8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a",
+            ],
+            q0: (
+                "ab640a12220d3ff283510ff3f4b1953d09fad35795140b1c5d64f313967934d5",
+                "dccb558863804a881d4fff3455716c836cef230e5209594ddd33d85c565b19b1",
+            ),
+            q1: (
+                "51cce63c50d972a6e51c61334f0f4875c9ac1cd2d3238412f84e31da7d980ef5",
+                "b45d1a36d00ad90e5ec7840a60a4de411917fbe7c82c3949a6e699e5a1b66aac",
+            ),
+            p: (
+                "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+                "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+            ),
+        },
+        RfcVector {
+            u: [
+                "afe47f2ea2b10465cc26ac403194dfb68b7f5ee865cda61e9f3e07a537220af1",
+                "379a27833b0bfe6f7bdca08e1e83c760bf9a338ab335542704edcd69ce9e46e0",
+            ],
+            q0: (
+                "5219ad0ddef3cc49b714145e91b2f7de6ce0a7a7dc7406c7726c7e373c58cb48",
+                "7950144e52d30acbec7b624c203b1996c99617d0b61c2442354301b191d93ecf",
+            ),
+            q1: (
+                "019b7cb4efcfeaf39f738fe638e31d375ad6837f58a852d032ff60c69ee3875f",
+                "589a62d2b22357fed5449bc38065b760095ebe6aeac84b01156ee4252715446e",
+            ),
+            p: (
+                "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+                "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+            ),
+        },
+        RfcVector {
+            u: [
+                "0fad9d125a9477d55cf9357105b0eb3a5c4259809bf87180aa01d651f53d312c",
+                "b68597377392cd3419d8fcc7d7660948c8403b19ea78bbca4b133c9d2196c0fb",
+            ],
+            q0: (
+                "a17bdf2965eb88074bc01157e644ed409dac97cfcf0c61c998ed0fa45e79e4a2",
+                "4f1bc80c70d411a3cc1d67aeae6e726f0f311639fee560c7f5a664554e3c9c2e",
+            ),
+            q1: (
+                "7da48bb67225c1a17d452c983798113f47e438e4202219dd0715f8419b274d66",
+                "b765696b2913e36db3016c47edb99e24b1da30e761a8a3215dc0ec4d8f96e6f9",
+            ),
+            p: (
+                "65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+                "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+            ),
+        },
+        RfcVector {
+            u: [
+                "3bbc30446f39a7befad080f4d5f32ed116b9534626993d2cc5033f6f8d805919",
+                "76bb02db019ca9d3c1e02f0c17f8baf617bbdae5c393a81d9ce11e3be1bf1d33",
+            ],
+            q0: (
+                "c76aaa823aeadeb3f356909cb08f97eee46ecb157c1f56699b5efebddf0e6398",
+                "776a6f45f528a0e8d289a4be12c4fab80762386ec644abf2bffb9b627e4352b1",
+            ),
+            q1: (
+                "418ac3d85a5ccc4ea8dec14f750a3a9ec8b85176c95a7022f391826794eb5a75",
+                "fd6604f69e9d9d2b74b072d14ea13050db72c932815523305cb9e807cc900aff",
+            ),
+            p: (
+                "4be61ee205094282ba8a2042bcb48d88dfbb609301c49aa8b078533dc65a0b5d",
+                "98f8df449a072c4721d241a3b1236d3caccba603f916ca680f4539d2bfb3c29e",
+            ),
+        },
+        RfcVector {
+            u: [
+                "4ebc95a6e839b1ae3c63b847798e85cb3c12d3817ec6ebc10af6ee51adb29fec",
+                "4e21af88e22ea80156aff790750121035b3eefaa96b425a8716e0d20b4e269ee",
+            ],
+            q0: (
+                "d88b989ee9d1295df413d4456c5c850b8b2fb0f5402cc5c4c7e815412e926db8",
+                "bb4a1edeff506cf16def96afff41b16fc74f6dbd55c2210e5b8f011ba32f4f40",
+            ),
+            q1: (
+                "a281e34e628f3a4d2a53fa87ff973537d68ad4fbc28d3be5e8d9f6a2571c5a4b",
+                "f6ed88a7aab56a488100e6f1174fa9810b47db13e86be999644922961206e184",
+            ),
+            p: (
+                "457ae2981f70ca85d8e24c308b14db22f3e3862c5ea0f652ca38b5e49cd64bc5",
+                "ecb9f0eadc9aeed232dabc53235368c1394c78de05dd96893eefa62b0f4757dc",
+            ),
+        },
+    ];
+
+    // RFC 9380, apendice J.8.1
+    const SECP256K1_VECTORS: [RfcVector; 5] = [
+        RfcVector {
+            u: [
+                "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+                "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+            ],
+            q0: (
+                "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e",
+                "c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936",
+            ),
+            q1: (
+                "44548adb1b399263ded3510554d28b4bead34b8cf9a37b4bd0bd2ba4db87ae63",
+                "96eb8e2faf05e368efe5957c6167001760233e6dd2487516b46ae725c4cce0c6",
+            ),
+            p: (
+                "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+        },
+        RfcVector {
+            u: [
+                "128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+                "5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00",
+            ],
+            q0: (
+                "07dd9432d426845fb19857d1b3a91722436604ccbbbadad8523b8fc38a5322d7",
+                "604588ef5138cffe3277bbd590b8550bcbe0e523bbaf1bed4014a467122eb33f",
+            ),
+            q1: (
+                "e9ef9794d15d4e77dde751e06c182782046b8dac05f8491eb88764fc65321f78",
+                "cb07ce53670d5314bf236ee2c871455c562dd76314aa41f012919fe8e7f717b3",
+            ),
+            p: (
+                "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+        },
+        RfcVector {
+            u: [
+                "ea67a7c02f2cd5d8b87715c169d055a22520f74daeb080e6180958380e2f98b9",
+                "7434d0d1a500d38380d1f9615c021857ac8d546925f5f2355319d823a478da18",
+            ],
+            q0: (
+                "576d43ab0260275adf11af990d130a5752704f79478628761720808862544b5d",
+                "643c4a7fb68ae6cff55edd66b809087434bbaff0c07f3f9ec4d49bb3c16623c3",
+            ),
+            q1: (
+                "f89d6d261a5e00fe5cf45e827b507643e67c2a947a20fd9ad71039f8b0e29ff8",
+                "b33855e0cc34a9176ead91c6c3acb1aacb1ce936d563bc1cee1dcffc806caf57",
+            ),
+            p: (
+                "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+            ),
+        },
+        RfcVector {
+            u: [
+                "eda89a5024fac0a8207a87e8cc4e85aa3bce10745d501a30deb87341b05bcdf5",
+                "dfe78cd116818fc2c16f3837fedbe2639fab012c407eac9dfe9245bf650ac51d",
+            ],
+            q0: (
+                "9c91513ccfe9520c9c645588dff5f9b4e92eaf6ad4ab6f1cd720d192eb58247a",
+                "c7371dcd0134412f221e386f8d68f49e7fa36f9037676e163d4a063fbf8a1fb8",
+            ),
+            q1: (
+                "10fee3284d7be6bd5912503b972fc52bf4761f47141a0015f1c6ae36848d869b",
+                "0b163d9b4bf21887364332be3eff3c870fa053cf508732900fc69a6eb0e1b672",
+            ),
+            p: (
+                "e2167bc785333a37aa562f021f1e881defb853839babf52a7f72b102e41890e9",
+                "f2401dd95cc35867ffed4f367cd564763719fbc6a53e969fb8496a1e6685d873",
+            ),
+        },
+        RfcVector {
+            u: [
+                "8d862e7e7e23d7843fe16d811d46d7e6480127a6b78838c277bca17df6900e9f",
+                "68071d2530f040f081ba818d3c7188a94c900586761e9115efa47ae9bd847938",
+            ],
+            q0: (
+                "b32b0ab55977b936f1e93fdc68cec775e13245e161dbfe556bbb1f72799b4181",
+                "2f5317098360b722f132d7156a94822641b615c91f8663be69169870a12af9e8",
+            ),
+            q1: (
+                "148f98780f19388b9fa93e7dc567b5a673e5fca7079cd9cdafd71982ec4c5e12",
+                "3989645d83a433bc0c001f3dac29af861f33a6fd1e04f4b36873f5bff497298a",
+            ),
+            p: (
+                "e3c8d35aaaf0b9b647e88a0a0a7ee5d5bed5ad38238152e4e6fd8c1f8cb7c998",
+                "8446eeb6181bf12f56a9d24e262221cc2f0c4725c7e3803024b5888ee5823aa6",
+            ),
+        },
+    ];
+
+    // RFC 9380, apendice J.1.2
+    const P256_NU_VECTORS: [RfcNuVector; 5] = [
+        RfcNuVector {
+            u: "b22d487045f80e9edcb0ecc8d4bf77833e2bf1f3a54004d7df1d57f4802d311f",
+            p: (
+                "f871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1",
+                "87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b",
+            ),
+        },
+        RfcNuVector {
+            u: "c7f96eadac763e176629b09ed0c11992225b3a5ae99479760601cbd69c221e58",
+            p: (
+                "fc3f5d734e8dce41ddac49f47dd2b8a57257522a865c124ed02b92b5237befa4",
+                "fe4d197ecf5a62645b9690599e1d80e82c500b22ac705a0b421fac7b47157866",
+            ),
+        },
+        RfcNuVector {
+            u: "314e8585fa92068b3ea2c3bab452d4257b38be1c097d58a21890456c2929614d",
+            p: (
+                "f164c6674a02207e414c257ce759d35eddc7f55be6d7f415e2cc177e5d8faa84",
+                "3aa274881d30db70485368c0467e97da0e73c18c1d00f34775d012b6fcee7f97",
+            ),
+        },
+        RfcNuVector {
+            u: "752d8eaa38cd785a799a31d63d99c2ae4261823b4a367b133b2c6627f48858ab",
+            p: (
+                "324532006312be4f162614076460315f7a54a6f85544da773dc659aca0311853",
+                "8d8197374bcd52de2acfefc8a54fe2c8d8bebd2a39f16be9b710e4b1af6ef883",
+            ),
+        },
+        RfcNuVector {
+            u: "0e1527840b9df2dfbef966678ff167140f2b27c4dccd884c25014dce0e41dfa3",
+            p: (
+                "5c4bad52f81f39c8e8de1260e9a06d72b8b00a0829a8ea004a610b0691bea5d9",
+                "c801e7c0782af1f74f24fc385a8555da0582032a3ce038de637ccdcb16f7ef7b",
+            ),
+        },
+    ];
+
+    // RFC 9380, apendice J.8.2
+    const SECP256K1_NU_VECTORS: [RfcNuVector; 5] = [
+        RfcNuVector {
+            u: "0137fcd23bc3da962e8808f97474d097a6c8aa2881fceef4514173635872cf3b",
+            p: (
+                "a4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b",
+                "62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7",
+            ),
+        },
+        RfcNuVector {
+            u: "e03f894b4d7caf1a50d6aa45cac27412c8867a25489e32c5ddeb503229f63a2e",
+            p: (
+                "3f3b5842033fff837d504bb4ce2a372bfeadbdbd84a1d2b678b6e1d7ee426b9d",
+                "902910d1fef15d8ae2006fc84f2a5a7bda0e0407dc913062c3a493c4f5d876a5",
+            ),
+        },
+        RfcNuVector {
+            u: "e7a6525ae7069ff43498f7f508b41c57f80563c1fe4283510b322446f32af41b",
+            p: (
+                "07644fa6281c694709f53bdd21bed94dab995671e4a8cd1904ec4aa50c59bfdf",
+                "c79f8d1dad79b6540426922f7fbc9579c3018dafeffcd4552b1626b506c21e7b",
+            ),
+        },
+        RfcNuVector {
+            u: "d97cf3d176a2f26b9614a704d7d434739d194226a706c886c5c3c39806bc323c",
+            p: (
+                "b734f05e9b9709ab631d960fa26d669c4aeaea64ae62004b9d34f483aa9acc33",
+                "03fc8a4a5a78632e2eb4d8460d69ff33c1d72574b79a35e402e801f2d0b1d6ee",
+            ),
+        },
+        RfcNuVector {
+            u: "a9ffbeee1d6e41ac33c248fb3364612ff591b502386c1bf6ac4aaf1ea51f8c3b",
+            p: (
+                "17d22b867658977b5002dbe8d0ee70a8cfddec3eec50fb93f36136070fd9fa6c",
+                "e9178ff02f4dab73480f8dd590328aea99856a7b6cc8e5a6cdf289ecc2a51718",
+            ),
+        },
+    ];
+
+    #[test]
+    fn test_02_p256_vectors() {
+        assert_vectors(
+            &P256_XMD_SHA256_SSWU,
+            b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_",
+            &P256_VECTORS,
+        );
+    }
+
+    #[test]
+    fn test_03_secp256k1_vectors() {
+        assert_vectors(
+            &SECP256K1_XMD_SHA256_SSWU,
+            b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_",
+            &SECP256K1_VECTORS,
+        );
+    }
+
+    #[test]
+    fn test_04_encode_to_curve() {
+        for (suite, vectors) in [
+            (&P256_XMD_SHA256_SSWU, &P256_NU_VECTORS),
+            (&SECP256K1_XMD_SHA256_SSWU, &SECP256K1_NU_VECTORS),
+        ] {
+            let params = suite.curve.domain_parameters::<BigInteger>().unwrap();
+            let dst = [
+                b"QUUX-V01-CS02-with-".as_slice(),
+                suite.name.as_bytes(),
+                b"_NU_",
+            ]
+            .concat();
+            for (msg, vector) in rfc_messages().iter().zip(vectors) {
+                let u = hash_to_field(msg, &dst, params.p, 1);
+                assert_eq!(u, vec![field(params.p, vector.u)]);
+                let (x, y) = vector.p;
+                let expected = params
+                    .curve
+                    .point(field(params.p, x), field(params.p, y))
+                    .unwrap();
+                assert_eq!(suite.map_to_curve(u[0]), expected);
+                assert_eq!(suite.encode_to_curve(msg, &dst), expected);
+            }
+            // otro DST da otro punto
+            assert_ne!(
+                suite.encode_to_curve(b"abc", &dst),
+                suite.encode_to_curve(b"abc", b"another-application")
+            );
+        }
+    }
+
+    #[test]
+    fn test_05_exceptional_inputs() {
+        // u = 0 anula Z^2 u^4 + Z u^2 y usa x1 = b / (Z a)
+        for suite in [&P256_XMD_SHA256_SSWU, &SECP256K1_XMD_SHA256_SSWU] {
+            let params = suite.curve.domain_parameters::<BigInteger>().unwrap();
+            let point = suite.map_to_curve(FiniteBody::new(params.p, BigInteger::zero()));
+            assert!(params.curve.contains(&point));
+            assert!(!point.is_identity());
+        }
+    }
+
+    #[test]
+    fn test_06_find_z_sswu() {
+        // los Z de los suites de P-256 y de la curva isogena a secp256k1
+        let params = SECP256R1.domain_parameters::<BigInteger>().unwrap();
+        let z = find_z_sswu(params.curve.a, params.curve.b);
+        assert_eq!(z, FiniteBody::new(params.p, BigInteger::from(-10)));
+
+        let isogeny = SECP256K1_XMD_SHA256_SSWU.isogeny.as_ref().unwrap();
+        let p = SECP256K1_XMD_SHA256_SSWU.p();
+        let (a, b) = (
+            FiniteBody::new(p, parse(isogeny.a)),
+            FiniteBody::new(p, parse(isogeny.b)),
+        );
+        assert_eq!(find_z_sswu(a, b), FiniteBody::new(p, BigInteger::from(-11)));
+    }
+
+    #[test]
+    fn test_07_hash_to_curve_on_any_curve_with_sswu() {
+        // con a * b != 0 coincide con el suite de P-256
+        let suite = &P256_XMD_SHA256_SSWU;
+        let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+        let params = suite.curve.domain_parameters::<BigInteger>().unwrap();
+        for msg in &rfc_messages()[..2] {
+            assert_eq!(
+                params.curve.hash_to_curve(msg, dst, BigInteger::one()),
+                Some(suite.hash_to_curve(msg, dst))
+            );
+        }
+    }
+
+    #[test]
+    fn test_08_try_and_increment() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let dst = b"try-and-increment";
+        let point = map_to_curve_try_and_increment(&params.curve, b"abc", dst, 64).unwrap();
+        assert!(params.curve.contains(&point));
+        assert_eq!(
+            params.curve.hash_to_curve(b"abc", dst, BigInteger::one()),
+            Some(point)
+        );
+        assert_ne!(
+            map_to_curve_try_and_increment(&params.curve, b"abcd", dst, 64),
+            Some(point)
+        );
+
+        // sin intentos no hay punto
+        assert_eq!(
+            map_to_curve_try_and_increment(&params.curve, b"abc", dst, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_09_hash_to_curve_clears_the_cofactor() {
+        // y^2 = x^3 + 7 sobre F_223 tiene a = 0 y cofactor 12
+        let params = TP1_P223.domain_parameters::<BigInteger>().unwrap();
+        for msg in rfc_messages() {
+            let point = params.curve.hash_to_curve(&msg, b"tp1", params.h).unwrap();
+            assert!(params.curve.contains(&point));
+            assert!(point.mul_scalar(params.n).is_identity());
+        }
+    }
+}
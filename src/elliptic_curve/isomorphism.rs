@@ -0,0 +1,375 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{
+    big_integer::BigInteger,
+    finite_body::{finite_body::FiniteBody, polynomial::Polynomial},
+};
+
+use super::{
+    curve::{one_like, small_multiple, zero_like, Curve},
+    point_elliptic_curve::{Coordinates, PointEllipticCurve},
+};
+
+/// Isomorfismo (x, y) -> (u^2 x, u^3 y) de y^2 = x^3 + ax + b en y^2 = x^3 + u^4 a x + u^6 b, con
+/// u != 0. Respeta la suma de puntos, asi que las dos curvas tienen los mismos ordenes de puntos y
+/// la misma estructura de grupo. Todo isomorfismo entre curvas en forma de Weierstrass reducida (en
+/// caracteristica distinta de 2 y 3) es de esta forma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveIsomorphism<T> {
+    pub u: T,
+    pub source: Curve<T>,
+    pub target: Curve<T>,
+}
+
+impl<T> CurveIsomorphism<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// El isomorfismo de escala u que sale de `source`. None si u = 0.
+    pub fn new(source: Curve<T>, u: T) -> Option<Self> {
+        if u.is_zero() {
+            return None;
+        }
+        let u2 = u * u;
+        Some(CurveIsomorphism {
+            u,
+            source,
+            target: Curve {
+                a: u2 * u2 * source.a,
+                b: u2 * u2 * u2 * source.b,
+            },
+        })
+    }
+
+    /// El isomorfismo de vuelta, con escala 1 / u.
+    pub fn inverse(&self) -> Self {
+        CurveIsomorphism {
+            u: one_like(self.u) / self.u,
+            source: self.target,
+            target: self.source,
+        }
+    }
+
+    /// (u^2 x, u^3 y), con el neutro en el neutro. None si `point` no es de `source`.
+    pub fn map(&self, point: &PointEllipticCurve<T>) -> Option<PointEllipticCurve<T>> {
+        if point.a != self.source.a || point.b != self.source.b {
+            return None;
+        }
+        let u2 = self.u * self.u;
+        let coordinates = match point.coordinates {
+            Coordinates::Identity => Coordinates::Identity,
+            Coordinates::Affine { x, y } => Coordinates::Affine {
+                x: u2 * x,
+                y: u2 * self.u * y,
+            },
+        };
+        Some(PointEllipticCurve {
+            coordinates,
+            a: self.target.a,
+            b: self.target.b,
+        })
+    }
+}
+
+impl<T> Curve<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// Una curva con j-invariante j: y^2 = x^3 + 1 si j = 0, y^2 = x^3 + x si j = 1728 y si no
+    /// a = 3k, b = 2k con k = j / (1728 - j). Las demas son isomorfas a esta o a uno de sus twists.
+    pub fn with_j_invariant(j: T) -> Self {
+        let (zero, one) = (zero_like(j), one_like(j));
+        let j_1728 = small_multiple(one, 1728);
+        if j.is_zero() {
+            return Curve { a: zero, b: one };
+        }
+        if j == j_1728 {
+            return Curve { a: one, b: zero };
+        }
+        let k = j / (j_1728 - j);
+        Curve {
+            a: small_multiple(k, 3),
+            b: small_multiple(k, 2),
+        }
+    }
+}
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>,
+{
+    /// Un isomorfismo sobre F_p de esta curva en `other`, si existe: un u con u^4 a = a' y
+    /// u^6 b = b'. Se buscan las raices de x^4 - a' / a (o de x^6 - b' / b si a = 0) y se prueba
+    /// cada una. Tener el mismo j-invariante es necesario pero no alcanza: la curva y su twist
+    /// cuadratico tienen el mismo j y no son isomorfas sobre F_p.
+    pub fn isomorphism_to(&self, other: &Self) -> Option<CurveIsomorphism<FiniteBody<X>>> {
+        if self.a.is_zero() != other.a.is_zero() || self.b.is_zero() != other.b.is_zero() {
+            return None;
+        }
+        let p = self.a.p;
+        let (degree, ratio) = match self.a.is_zero() {
+            false => (4, other.a / self.a),
+            true => (6, other.b / self.b),
+        };
+        let mut coefficients = vec![FiniteBody::new(p, X::zero()); degree + 1];
+        coefficients[0] = -ratio;
+        coefficients[degree] = FiniteBody::new(p, X::one());
+
+        Polynomial::from_coefficients(p, coefficients)
+            .roots()
+            .into_iter()
+            .filter_map(|u| CurveIsomorphism::new(*self, u))
+            .find(|isomorphism| isomorphism.target == *other)
+    }
+
+    pub fn is_isomorphic_to(&self, other: &Self) -> bool {
+        self.isomorphism_to(other).is_some()
+    }
+
+    /// Twist cuadratico y^2 = x^3 + a d^2 x + b d^3, con d el menor no residuo cuadratico: para cada
+    /// x, E tiene 1 + χ puntos y el twist 1 - χ, por eso #E + #E' = 2p + 2. Es isomorfo a E sobre
+    /// F_p(sqrt(d)), y sobre F_p solo si j = 1728 y p = 3 mod 4 (ahi d^2 es una potencia cuarta).
+    pub fn quadratic_twist(&self) -> Self {
+        let d = self.twist_generator(2);
+        Curve {
+            a: self.a * d * d,
+            b: self.b * d * d * d,
+        }
+    }
+
+    /// Los twists de la curva salvo isomorfismo sobre F_p, empezando por ella misma: las curvas con
+    /// el mismo j-invariante, que se vuelven isomorfas sobre una extension de F_p.
+    ///
+    /// - j = 0: y^2 = x^3 + b d^i, los seis twists sexticos (i < 6) si p = 1 mod 3 y dos si no.
+    /// - j = 1728: y^2 = x^3 + a d^i x, los cuatro twists cuarticos (i < 4) si p = 1 mod 4 y dos si
+    ///   no (con p = 3 mod 4 el twist cuadratico es isomorfo a la curva, el otro es y^2 = x^3 + adx).
+    /// - En otro caso, la curva y su `quadratic_twist`.
+    ///
+    /// d genera F_p^* modulo las potencias sextas (o cuartas), asi las curvas son dos a dos no
+    /// isomorfas.
+    pub fn twists(&self) -> Vec<Self> {
+        let p = self.a.p;
+        let one = X::one();
+        let divides = |m: X| (p - one) % m == X::zero();
+        let three = one + one + one;
+        let degree = match (self.a.is_zero(), self.b.is_zero()) {
+            (true, _) if divides(three) => 6,
+            (_, true) if divides(three + one) => 4,
+            (true, _) | (_, true) => 2,
+            _ => return vec![*self, self.quadratic_twist()],
+        };
+
+        let d = self.twist_generator(degree);
+        let mut power = one_like(d);
+        (0..degree)
+            .map(|_| {
+                let twist = match self.a.is_zero() {
+                    true => Curve {
+                        a: self.a,
+                        b: self.b * power,
+                    },
+                    false => Curve {
+                        a: self.a * power,
+                        b: self.b,
+                    },
+                };
+                power = power * d;
+                twist
+            })
+            .collect()
+    }
+
+    // El menor d >= 2 que genera F_p^* / (F_p^*)^m, para m = 2, 4 o 6 dividiendo a p - 1: un no
+    // residuo cuadratico que, para m = 6, ademas no es un cubo.
+    fn twist_generator(&self, m: usize) -> FiniteBody<X> {
+        let p = self.a.p;
+        let one = X::one();
+        let three = one + one + one;
+        let is_generator = |d: &FiniteBody<X>| {
+            d.legendre() == -1 && (m != 6 || d.pow_mod((p - one) / three).value != one)
+        };
+        let mut d = FiniteBody::new(p, one + one);
+        while !is_generator(&d) {
+            d = FiniteBody::new(p, d.value + one);
+        }
+        d
+    }
+}
+
+#[cfg(test)]
+mod test_isomorphism {
+    use super::*;
+
+    fn curve(p: i64, a: i64, b: i64) -> Curve<FiniteBody<i64>> {
+        Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap()
+    }
+
+    // Cada curva de la lista es isomorfa a exactamente una de `twists`
+    fn assert_classes(twists: &[Curve<FiniteBody<i64>>], curves: &[Curve<FiniteBody<i64>>]) {
+        for other in curves {
+            let matches = twists
+                .iter()
+                .filter(|twist| other.is_isomorphic_to(twist))
+                .count();
+            assert_eq!(matches, 1, "{:?}", other);
+        }
+    }
+
+    #[test]
+    fn test_01_isomorphism_maps_points() {
+        let p = 43;
+        let source = curve(p, 3, 10);
+        let u = FiniteBody::new(p, 7);
+        let target = CurveIsomorphism::new(source, u).unwrap().target;
+        assert_eq!(target, curve(p, 3 * 7_i64.pow(4), 10 * 7_i64.pow(6)));
+
+        let isomorphism = source.isomorphism_to(&target).unwrap();
+        assert_eq!(isomorphism.u * isomorphism.u, u * u);
+        let points: Vec<_> = source.points().collect();
+        for point in &points {
+            let image = isomorphism.map(point).unwrap();
+            if let Some((x, y)) = image.coordinates() {
+                assert!(target.point(x, y).is_some());
+            }
+            assert_eq!(isomorphism.inverse().map(&image), Some(*point));
+            for other in points.iter().step_by(5) {
+                assert_eq!(
+                    isomorphism.map(&(*point + *other).unwrap()),
+                    image + isomorphism.map(other).unwrap()
+                );
+            }
+        }
+        assert_eq!(isomorphism.map(&target.identity()), None);
+        assert_eq!(source.count_points(), target.count_points());
+        assert_eq!(CurveIsomorphism::new(source, FiniteBody::new(p, 0)), None);
+    }
+
+    #[test]
+    fn test_02_quadratic_twist() {
+        for (p, a, b) in [(43, 3, 10), (1021, 905, 100), (1019, 0, 7)] {
+            let curve = curve(p, a, b);
+            let twist = curve.quadratic_twist();
+            assert_eq!(twist.j_invariant(), curve.j_invariant());
+            assert!(!curve.is_isomorphic_to(&twist));
+            assert_eq!(
+                curve.count_points() + twist.count_points(),
+                2 * p as usize + 2
+            );
+            assert!(curve.twists()[1].is_isomorphic_to(&twist));
+        }
+        // j = 1728 con p = 3 mod 4: el twist cuadratico es isomorfo a la curva, pero hay otro
+        let curve1728 = curve(1019, -1, 0);
+        assert!(curve1728.is_isomorphic_to(&curve1728.quadratic_twist()));
+        let twists = curve1728.twists();
+        assert_eq!(twists.len(), 2);
+        assert!(!twists[0].is_isomorphic_to(&twists[1]));
+        for twist in twists {
+            assert_eq!(twist.count_points(), 1020);
+        }
+        assert_eq!(curve(1019, 0, 7).twists().len(), 2);
+        assert_eq!(curve(43, 0, 7).twists().len(), 6);
+        assert_eq!(curve(43, 5, 0).twists().len(), 2);
+
+        // las curvas con el j de (905, 100) son las (t^2 a, t^3 b) con t != 0, y cada una es isomorfa
+        // a ella (t un cuadrado) o a su twist (t un no residuo)
+        let p = 1021;
+        let base = curve(p, 905, 100);
+        let same_j: Vec<_> = (1..p)
+            .map(|t| curve(p, t * t % p * 905, t * t % p * t % p * 100))
+            .collect();
+        for other in &same_j {
+            assert_eq!(other.j_invariant(), base.j_invariant());
+        }
+        assert_classes(&base.twists(), &same_j);
+    }
+
+    #[test]
+    fn test_03_sextic_twists() {
+        // y^2 = x^3 + b sobre F_223 (p = 1 mod 3): seis clases segun b modulo las potencias sextas
+        let p = 223;
+        let course = curve(p, 0, 7);
+        let twists = course.twists();
+        assert_eq!(twists.len(), 6);
+        assert_eq!(twists[0], course);
+        let all: Vec<_> = (1..p).map(|b| curve(p, 0, b)).collect();
+        assert_classes(&twists, &all);
+
+        // los seis ordenes son distintos y cumplen la cota de Hasse
+        let mut orders: Vec<_> = twists.iter().map(|twist| twist.count_points()).collect();
+        for order in &orders {
+            assert!((p + 1 - *order as i64).pow(2) <= 4 * p);
+        }
+        orders.sort();
+        orders.dedup();
+        assert_eq!(orders.len(), 6);
+
+        // por eso G = (47, 71) de y^2 = x^3 + 7 tiene el mismo orden que su imagen en y^2 = x^3 + 7u^6
+        let g = course
+            .point(FiniteBody::new(p, 47), FiniteBody::new(p, 71))
+            .unwrap();
+        let isomorphism = CurveIsomorphism::new(course, FiniteBody::new(p, 5)).unwrap();
+        let image = isomorphism.map(&g).unwrap();
+        assert_eq!(
+            g.order_with_group_order(252),
+            image.order_with_group_order(252)
+        );
+    }
+
+    #[test]
+    fn test_04_quartic_twists() {
+        // y^2 = x^3 + ax sobre F_1009 (p = 1 mod 4): cuatro clases segun a modulo las potencias cuartas
+        let p = 1009;
+        let twists = curve(p, -1, 0).twists();
+        assert_eq!(twists.len(), 4);
+        let all: Vec<_> = (1..p).map(|a| curve(p, a, 0)).collect();
+        assert_classes(&twists, &all);
+        for twist in &twists {
+            assert_eq!(twist.j_invariant(), Some(FiniteBody::new(p, 1728)));
+        }
+        // el twist cuadratico es el de i = 2
+        assert!(twists[2].is_isomorphic_to(&twists[0].quadratic_twist()));
+    }
+
+    #[test]
+    fn test_05_with_j_invariant() {
+        let p = 1021;
+        for j in [0, 1, 2, 500, 1020, 1728 % p] {
+            let j = FiniteBody::new(p, j);
+            let curve = Curve::<FiniteBody<i64>>::with_j_invariant(j);
+            assert_eq!(curve.j_invariant(), Some(j));
+        }
+        // (905, 100) es isomorfa a la curva de su j o a su twist
+        let curve = curve(p, 905, 100);
+        let base = Curve::with_j_invariant(curve.j_invariant().unwrap());
+        assert!(curve.is_isomorphic_to(&base) || curve.is_isomorphic_to(&base.quadratic_twist()));
+    }
+}
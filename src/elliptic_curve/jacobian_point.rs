@@ -0,0 +1,529 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use super::{
+    curve::{conditional_swap, one_like, zero_like},
+    operation_count::{record_addition, record_doubling},
+    point_elliptic_curve::PointEllipticCurve,
+};
+
+/// Punto en coordenadas jacobianas (X : Y : Z), que representa al punto afin (X / Z^2, Y / Z^3).
+/// Z = 0 es el punto del infinito.
+///
+/// Las formulas de suma y duplicacion no dividen, asi que una multiplicacion escalar hace una sola
+/// inversion (al volver a coordenadas afines) en lugar de una por cada suma. Las formulas son las de
+/// la Explicit-Formulas Database (https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html).
+#[derive(Debug, Clone, Copy)]
+pub struct JacobianPoint<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> JacobianPoint<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Punto del infinito (1 : 1 : 0).
+    pub fn identity(a: T, b: T) -> Self {
+        JacobianPoint {
+            x: one_like(a),
+            y: one_like(a),
+            z: zero_like(a),
+            a,
+            b,
+        }
+    }
+
+    pub fn from_affine(point: &PointEllipticCurve<T>) -> Self {
+        match point.coordinates() {
+            Some((x, y)) => JacobianPoint {
+                x,
+                y,
+                z: one_like(x),
+                a: point.a,
+                b: point.b,
+            },
+            None => Self::identity(point.a, point.b),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Vuelve a coordenadas afines con una sola inversion.
+    pub fn to_affine(&self) -> PointEllipticCurve<T> {
+        if self.is_identity() {
+            return PointEllipticCurve::new_inf(self.a, self.b);
+        }
+        let z_inv = one_like(self.z) / self.z;
+        self.normalize_with_inverse(z_inv)
+    }
+
+    // Las formulas solo valen si la division de T es exacta (ver `has_exact_division`); con enteros
+    // el punto que resulta no esta en la curva
+    fn normalize_with_inverse(&self, z_inv: T) -> PointEllipticCurve<T> {
+        let z_inv_2 = z_inv * z_inv;
+        let (x, y) = (self.x * z_inv_2, self.y * z_inv_2 * z_inv);
+        debug_assert!(
+            y.pow(2) == x.pow(3) + self.a * x + self.b,
+            "the normalized point is not on the curve"
+        );
+        PointEllipticCurve::new_unchecked(x, y, self.a, self.b)
+    }
+
+    /// Pasa varios puntos a coordenadas afines con una sola inversion en total (truco de Montgomery):
+    /// se invierte el producto de todos los Z y de ahi se despejan los inversos de cada uno.
+    pub fn batch_normalize(points: &[JacobianPoint<T>]) -> Vec<PointEllipticCurve<T>> {
+        let finite: Vec<&JacobianPoint<T>> = points.iter().filter(|p| !p.is_identity()).collect();
+        if finite.is_empty() {
+            return points.iter().map(|p| p.to_affine()).collect();
+        }
+
+        // prefix[i] = z_0 * z_1 * ... * z_i
+        let mut prefix = Vec::with_capacity(finite.len());
+        let mut acum = finite[0].z;
+        prefix.push(acum);
+        for point in finite.iter().skip(1) {
+            acum = acum * point.z;
+            prefix.push(acum);
+        }
+
+        let mut inverse = one_like(acum) / acum;
+        let mut z_inverses = vec![inverse; finite.len()];
+        for i in (1..finite.len()).rev() {
+            z_inverses[i] = inverse * prefix[i - 1];
+            inverse = inverse * finite[i].z;
+        }
+        z_inverses[0] = inverse;
+
+        let mut z_inverses = z_inverses.into_iter();
+        points
+            .iter()
+            .map(|point| match point.is_identity() {
+                true => point.to_affine(),
+                false => point.normalize_with_inverse(z_inverses.next().unwrap()),
+            })
+            .collect()
+    }
+
+    pub fn double(&self) -> Self {
+        if self.is_identity() {
+            return *self;
+        }
+        record_doubling();
+        // si Y = 0 las formulas dan Z3 = 2 * Y * Z = 0, el punto del infinito
+        if self.a.is_zero() {
+            self.double_a_zero()
+        } else if is_minus_three(self.a) {
+            self.double_a_minus_three()
+        } else {
+            self.double_generic()
+        }
+    }
+
+    // dbl-2007-bl
+    fn double_generic(&self) -> Self {
+        let xx = self.x * self.x;
+        let yy = self.y * self.y;
+        let yyyy = yy * yy;
+        let zz = self.z * self.z;
+        let s = ((self.x + yy).pow(2) - xx - yyyy) * 2_isize;
+        let m = xx * 3_isize + self.a * zz * zz;
+        let t = m * m - s * 2_isize;
+        JacobianPoint {
+            x: t,
+            y: m * (s - t) - yyyy * 8_isize,
+            z: (self.y + self.z).pow(2) - yy - zz,
+            a: self.a,
+            b: self.b,
+        }
+    }
+
+    // dbl-2001-b, para a = -3: 3X^2 + aZ^4 = 3(X - Z^2)(X + Z^2)
+    fn double_a_minus_three(&self) -> Self {
+        let delta = self.z * self.z;
+        let gamma = self.y * self.y;
+        let beta = self.x * gamma;
+        let alpha = (self.x - delta) * (self.x + delta) * 3_isize;
+        let x3 = alpha * alpha - beta * 8_isize;
+        JacobianPoint {
+            x: x3,
+            y: alpha * (beta * 4_isize - x3) - gamma * gamma * 8_isize,
+            z: (self.y + self.z).pow(2) - gamma - delta,
+            a: self.a,
+            b: self.b,
+        }
+    }
+
+    // dbl-2009-l, para a = 0
+    fn double_a_zero(&self) -> Self {
+        let a = self.x * self.x;
+        let b = self.y * self.y;
+        let c = b * b;
+        let d = ((self.x + b).pow(2) - a - c) * 2_isize;
+        let e = a * 3_isize;
+        let x3 = e * e - d * 2_isize;
+        JacobianPoint {
+            x: x3,
+            y: e * (d - x3) - c * 8_isize,
+            z: self.y * self.z * 2_isize,
+            a: self.a,
+            b: self.b,
+        }
+    }
+
+    /// Suma de dos puntos jacobianos (add-2007-bl).
+    pub fn add_jacobian(&self, other: &JacobianPoint<T>) -> Self {
+        if self.a != other.a || self.b != other.b {
+            panic!("Points are not on the same curve");
+        }
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+
+        let z1z1 = self.z * self.z;
+        let z2z2 = other.z * other.z;
+        let u1 = self.x * z2z2;
+        let u2 = other.x * z1z1;
+        let s1 = self.y * other.z * z2z2;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - u1;
+        let r = (s2 - s1) * 2_isize;
+        if h.is_zero() {
+            // mismo x afin: es el mismo punto o su opuesto
+            return match r.is_zero() {
+                true => self.double(),
+                false => Self::identity(self.a, self.b),
+            };
+        }
+
+        record_addition();
+        let i = (h * 2_isize).pow(2);
+        let j = h * i;
+        let v = u1 * i;
+        let x3 = r * r - j - v * 2_isize;
+        JacobianPoint {
+            x: x3,
+            y: r * (v - x3) - s1 * j * 2_isize,
+            z: ((self.z + other.z).pow(2) - z1z1 - z2z2) * h,
+            a: self.a,
+            b: self.b,
+        }
+    }
+
+    /// Suma con un punto afin (Z2 = 1), que ahorra varias multiplicaciones (madd-2007-bl).
+    pub fn add_mixed(&self, other: &PointEllipticCurve<T>) -> Self {
+        if self.a != other.a || self.b != other.b {
+            panic!("Points are not on the same curve");
+        }
+        let (x2, y2) = match other.coordinates() {
+            Some((x2, y2)) => (x2, y2),
+            None => return *self,
+        };
+        if self.is_identity() {
+            return Self::from_affine(other);
+        }
+
+        let z1z1 = self.z * self.z;
+        let u2 = x2 * z1z1;
+        let s2 = y2 * self.z * z1z1;
+        let h = u2 - self.x;
+        let r = (s2 - self.y) * 2_isize;
+        if h.is_zero() {
+            return match r.is_zero() {
+                true => self.double(),
+                false => Self::identity(self.a, self.b),
+            };
+        }
+
+        record_addition();
+        let hh = h * h;
+        let i = hh * 4_isize;
+        let j = h * i;
+        let v = self.x * i;
+        let x3 = r * r - j - v * 2_isize;
+        JacobianPoint {
+            x: x3,
+            y: r * (v - x3) - self.y * j * 2_isize,
+            z: (self.z + h).pow(2) - z1z1 - hh,
+            a: self.a,
+            b: self.b,
+        }
+    }
+
+    /// Intercambia `self` y `other` si `choice` es 1 y los deja igual si es 0, sin bifurcar segun
+    /// `choice` (ver `curve::conditional_swap`).
+    pub fn conditional_swap(&mut self, other: &mut Self, choice: T) {
+        conditional_swap(&mut self.x, &mut other.x, choice);
+        conditional_swap(&mut self.y, &mut other.y, choice);
+        conditional_swap(&mut self.z, &mut other.z, choice);
+    }
+}
+
+// a == -3, sin construir la constante 3 (T::one() de FiniteBody no conoce el modulo, pero al sumarlo
+// a la derecha se usa el modulo de a)
+fn is_minus_three<T>(a: T) -> bool
+where
+    T: Add<Output = T> + Copy + Zero + One,
+{
+    (a + T::one() + T::one() + T::one()).is_zero()
+}
+
+impl<T> Add for JacobianPoint<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.add_jacobian(&other)
+    }
+}
+
+impl<T> Neg for JacobianPoint<T>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        JacobianPoint { y: -self.y, ..self }
+    }
+}
+
+impl<T> PartialEq for JacobianPoint<T>
+where
+    T: Add<T> + Sub<Output = T> + Mul<Output = T> + PartialEq + Copy + Zero,
+{
+    /// Igualdad proyectiva: (X1 : Y1 : Z1) = (X2 : Y2 : Z2) si X1 Z2^2 = X2 Z1^2 y Y1 Z2^3 = Y2 Z1^3.
+    fn eq(&self, other: &Self) -> bool {
+        if self.a != other.a || self.b != other.b {
+            return false;
+        }
+        match (self.z.is_zero(), other.z.is_zero()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = self.z * self.z;
+                let z2z2 = other.z * other.z;
+                self.x * z2z2 == other.x * z1z1
+                    && self.y * z2z2 * other.z == other.y * z1z1 * self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<T> From<PointEllipticCurve<T>> for JacobianPoint<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    fn from(point: PointEllipticCurve<T>) -> Self {
+        JacobianPoint::from_affine(&point)
+    }
+}
+
+impl<T> From<JacobianPoint<T>> for PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    fn from(point: JacobianPoint<T>) -> Self {
+        point.to_affine()
+    }
+}
+
+#[cfg(test)]
+mod test_jacobian_point {
+    use crate::finite_body::finite_body::FiniteBody;
+
+    use super::*;
+
+    fn point(p: i32, x: i32, y: i32, a: i32, b: i32) -> PointEllipticCurve<FiniteBody<i32>> {
+        PointEllipticCurve::new(
+            FiniteBody::new(p, x),
+            FiniteBody::new(p, y),
+            FiniteBody::new(p, a),
+            FiniteBody::new(p, b),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_01_affine_round_trip() {
+        let g = point(223, 47, 71, 0, 7);
+        let jacobian = JacobianPoint::from(g);
+        assert_eq!(jacobian.to_affine(), g);
+
+        // (X : Y : Z) y (l^2 X : l^3 Y : l Z) son el mismo punto
+        let l = FiniteBody::new(223, 5);
+        let scaled = JacobianPoint {
+            x: jacobian.x * l.pow(2),
+            y: jacobian.y * l.pow(3),
+            z: jacobian.z * l,
+            ..jacobian
+        };
+        assert_eq!(scaled, jacobian);
+        assert_eq!(scaled.to_affine(), g);
+    }
+
+    #[test]
+    fn test_02_double_matches_affine_for_a_zero() {
+        let g = point(223, 47, 71, 0, 7);
+        let mut affine = g;
+        let mut jacobian = JacobianPoint::from(g);
+        for _ in 0..10 {
+            affine = (affine + affine).unwrap();
+            jacobian = jacobian.double();
+            assert_eq!(jacobian.to_affine(), affine);
+        }
+    }
+
+    #[test]
+    fn test_03_double_matches_affine_for_a_minus_three() {
+        let g = point(1021, 379, 1011, -3, -3);
+        let mut affine = g;
+        let mut jacobian = JacobianPoint::from(g);
+        for _ in 0..10 {
+            affine = (affine + affine).unwrap();
+            jacobian = jacobian.double();
+            assert_eq!(jacobian.to_affine(), affine);
+        }
+    }
+
+    #[test]
+    fn test_04_double_matches_affine_for_generic_a() {
+        let g = point(1021, 1006, 416, 905, 100);
+        let mut affine = g;
+        let mut jacobian = JacobianPoint::from(g);
+        for _ in 0..10 {
+            affine = (affine + affine).unwrap();
+            jacobian = jacobian.double();
+            assert_eq!(jacobian.to_affine(), affine);
+        }
+    }
+
+    #[test]
+    fn test_05_add_and_mixed_add_match_affine() {
+        let g = point(1021, 1006, 416, 905, 100);
+        let mut affine = g;
+        let mut jacobian = JacobianPoint::from(g);
+        let mut mixed = JacobianPoint::from(g);
+        for _ in 0..50 {
+            affine = (affine + g).unwrap();
+            jacobian = jacobian + JacobianPoint::from(g).double() + -JacobianPoint::from(g);
+            mixed = mixed.add_mixed(&g);
+            assert_eq!(jacobian.to_affine(), affine);
+            assert_eq!(mixed.to_affine(), affine);
+        }
+
+        // suma de dos puntos iguales con Z distinto de 1
+        assert_eq!(mixed + jacobian, mixed.double());
+    }
+
+    #[test]
+    fn test_06_special_cases() {
+        let g = point(223, 15, 86, 0, 7); // orden 7
+        let identity = JacobianPoint::identity(g.a, g.b);
+        let jacobian = JacobianPoint::from(g);
+
+        assert!(identity.is_identity());
+        assert_eq!(identity.to_affine(), PointEllipticCurve::new_inf(g.a, g.b));
+        assert_eq!(jacobian + identity, jacobian);
+        assert_eq!(identity.add_mixed(&g), jacobian);
+        assert!((jacobian + -jacobian).is_identity());
+        assert!(jacobian.add_mixed(&-g).is_identity());
+        assert_eq!(jacobian.add_mixed(&g), jacobian.double());
+
+        let mut acum = identity;
+        for _ in 0..7 {
+            acum = acum.add_mixed(&g);
+        }
+        assert!(acum.is_identity());
+    }
+
+    #[test]
+    fn test_07_double_point_of_order_two() {
+        // y^2 = x^3 + 7 sobre F_223 tiene (x, 0) con x^3 = -7
+        let p = 223;
+        let x = (0..p)
+            .find(|x| (FiniteBody::new(p, *x).pow(3) + FiniteBody::new(p, 7)).is_zero())
+            .unwrap();
+        let order_two = point(p, x, 0, 0, 7);
+        assert!(JacobianPoint::from(order_two).double().is_identity());
+    }
+
+    #[test]
+    fn test_08_batch_normalize() {
+        let g = point(1021, 379, 1011, -3, -3);
+        let mut points = vec![JacobianPoint::identity(g.a, g.b)];
+        let mut acum = JacobianPoint::from(g);
+        for _ in 0..20 {
+            points.push(acum);
+            acum = acum.double().add_mixed(&g);
+        }
+        points.push(JacobianPoint::identity(g.a, g.b));
+
+        let normalized = JacobianPoint::batch_normalize(&points);
+        let expected: Vec<_> = points.iter().map(|p| p.to_affine()).collect();
+        assert_eq!(normalized, expected);
+    }
+
+    #[test]
+    fn test_09_conditional_swap() {
+        let g = point(1021, 379, 1011, -3, -3);
+        let (zero, one) = (FiniteBody::new(1021, 0), FiniteBody::new(1021, 1));
+        let mut p = JacobianPoint::from(g).double();
+        let mut q = JacobianPoint::from(g);
+
+        p.conditional_swap(&mut q, zero);
+        assert_eq!(p.to_affine(), (g + g).unwrap());
+        assert_eq!(q.to_affine(), g);
+
+        p.conditional_swap(&mut q, one);
+        assert_eq!(p.to_affine(), g);
+        assert_eq!(q.to_affine(), (g + g).unwrap());
+    }
+}
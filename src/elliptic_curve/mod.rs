@@ -1,2 +1,28 @@
+pub mod birational;
+pub mod curve;
 pub mod discrete_logarithm_problem;
+pub mod division_polynomial;
+pub mod edwards;
+pub mod error;
+pub mod fixed_base;
+pub mod glv;
+pub mod group_order;
+pub mod group_structure;
+pub mod hash_to_curve;
+pub mod isomorphism;
+pub mod jacobian_point;
+pub mod montgomery;
+pub mod msm;
+pub mod naf;
+pub mod named_curves;
+pub mod operation_count;
+pub mod point_counting;
 pub mod point_elliptic_curve;
+pub mod point_order;
+pub mod points;
+pub mod rfc7748;
+pub mod scalar;
+pub mod scalar_multiplication;
+pub mod schoof;
+pub mod sec1;
+pub mod singular_cubic;
@@ -0,0 +1,376 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{
+    curve::{conditional_swap, one_like, small_multiple, zero_like},
+    error::EllipticCurveError,
+    point_elliptic_curve::Coordinates,
+};
+
+/// Curva eliptica en forma de Montgomery: By^2 = x^3 + Ax^2 + x.
+///
+/// La escalera usa solo la coordenada x (u en RFC 7748): x(kP) se calcula a partir de x(P) sin
+/// conocer y, y da lo mismo para P y -P. Curve25519 y Curve448 tienen esta forma. Los puntos con
+/// las dos coordenadas son `MontgomeryPoint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MontgomeryCurve<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> MontgomeryCurve<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// Curva By^2 = x^3 + Ax^2 + x. Falla si B = 0 o si A^2 = 4, que es cuando x^3 + Ax^2 + x tiene
+    /// una raiz doble.
+    pub fn new(a: T, b: T) -> Result<Self, EllipticCurveError> {
+        if b.is_zero() || (a * a - small_multiple(one_like(a), 4)).is_zero() {
+            return Err(EllipticCurveError::SingularCurve);
+        }
+        Ok(MontgomeryCurve { a, b })
+    }
+
+    /// Punto (x, y) de la curva, o None si no satisface la ecuacion.
+    pub fn point(&self, x: T, y: T) -> Option<MontgomeryPoint<T>> {
+        if self.b * y * y != x * x * x + self.a * x * x + x {
+            return None;
+        }
+        Some(MontgomeryPoint {
+            coordinates: Coordinates::Affine { x, y },
+            a: self.a,
+            b: self.b,
+        })
+    }
+
+    /// Punto del infinito, el neutro del grupo.
+    pub fn identity(&self) -> MontgomeryPoint<T> {
+        MontgomeryPoint {
+            coordinates: Coordinates::Identity,
+            a: self.a,
+            b: self.b,
+        }
+    }
+
+    pub fn contains(&self, point: &MontgomeryPoint<T>) -> bool {
+        point.a == self.a && point.b == self.b
+    }
+
+    /// (A - 2) / 4, la constante de la duplicacion en la escalera de RFC 7748.
+    pub fn a24(&self) -> T {
+        let one = one_like(self.a);
+        (self.a - one - one) / small_multiple(one, 4)
+    }
+}
+
+impl<X> MontgomeryCurve<FiniteBody<X>>
+where
+    X: Copy + From<u8>,
+    FiniteBody<X>: Add<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Mul<Output = FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    /// x(kP) a partir de x = x(P) con la escalera de Montgomery en coordenadas proyectivas
+    /// (X : Z), recorriendo `bits` bits de |k| (RFC 7748, 5). Hace una duplicacion y una suma
+    /// diferencial por bit sin importar su valor. Si kP es el punto del infinito devuelve 0, como
+    /// RFC 7748 (el punto (0, 0) de orden 2 tambien tiene x = 0).
+    pub fn ladder<K: Into<BigInteger>>(
+        &self,
+        k: K,
+        x: FiniteBody<X>,
+        bits: usize,
+    ) -> FiniteBody<X> {
+        let k = k.into().abs();
+        let a24 = self.a24();
+
+        // Invariante: (x_3 : z_3) = x((m + 1)P) si (x_2 : z_2) = x(mP), con diferencia P
+        let (mut x_2, mut z_2) = (one_like(x), zero_like(x));
+        let (mut x_3, mut z_3) = (x, one_like(x));
+        let mut swapped = false;
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            let choice = FiniteBody::from_bit(x.p, swapped ^ bit);
+            conditional_swap(&mut x_2, &mut x_3, choice);
+            conditional_swap(&mut z_2, &mut z_3, choice);
+            swapped = bit;
+
+            let sum_2 = x_2 + z_2;
+            let sum_2_squared = sum_2 * sum_2;
+            let difference_2 = x_2 - z_2;
+            let difference_2_squared = difference_2 * difference_2;
+            let e = sum_2_squared - difference_2_squared;
+            let sum_3 = x_3 + z_3;
+            let difference_3 = x_3 - z_3;
+            let da = difference_3 * sum_2;
+            let cb = sum_3 * difference_2;
+
+            x_3 = (da + cb) * (da + cb);
+            z_3 = x * (da - cb) * (da - cb);
+            x_2 = sum_2_squared * difference_2_squared;
+            z_2 = e * (sum_2_squared + a24 * e);
+        }
+        let choice = FiniteBody::from_bit(x.p, swapped);
+        conditional_swap(&mut x_2, &mut x_3, choice);
+        conditional_swap(&mut z_2, &mut z_3, choice);
+
+        match z_2.is_zero() {
+            true => zero_like(x),
+            false => x_2 / z_2,
+        }
+    }
+}
+
+/// Punto afin de una curva de Montgomery, con las dos coordenadas. Igual que `PointEllipticCurve`,
+/// lleva consigo los coeficientes A y B.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MontgomeryPoint<T> {
+    pub coordinates: Coordinates<T>,
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> MontgomeryPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    pub fn is_identity(&self) -> bool {
+        self.coordinates == Coordinates::Identity
+    }
+
+    /// (x, y), o None si es el punto del infinito.
+    pub fn coordinates(&self) -> Option<(T, T)> {
+        match self.coordinates {
+            Coordinates::Affine { x, y } => Some((x, y)),
+            Coordinates::Identity => None,
+        }
+    }
+
+    /// k * P con "double and add" en coordenadas afines. Si k es negativo el resultado es
+    /// -(|k| * P).
+    pub fn mul_scalar<K: Into<BigInteger>>(&self, k: K) -> Self {
+        let k = k.into();
+        let magnitude = k.abs();
+
+        let mut result = MontgomeryPoint {
+            coordinates: Coordinates::Identity,
+            ..*self
+        };
+        for i in (0..magnitude.bits()).rev() {
+            result = result + result;
+            if magnitude.bit(i) {
+                result = result + *self;
+            }
+        }
+        match k.is_negative() {
+            true => -result,
+            false => result,
+        }
+    }
+}
+
+impl<T> Add for MontgomeryPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    /// Cuerda y tangente: con l la pendiente, x3 = Bl^2 - A - x1 - x2 y y3 = l(x1 - x3) - y1. La
+    /// tangente tiene pendiente (3x1^2 + 2Ax1 + 1) / 2By1.
+    fn add(self, other: Self) -> Self::Output {
+        let (Some((x1, y1)), Some((x2, y2))) = (self.coordinates(), other.coordinates()) else {
+            return match self.is_identity() {
+                true => other,
+                false => self,
+            };
+        };
+        if x1 == x2 && (y1 + y2).is_zero() {
+            return MontgomeryPoint {
+                coordinates: Coordinates::Identity,
+                ..self
+            };
+        }
+
+        let slope = match x1 == x2 {
+            true => {
+                let numerator =
+                    small_multiple(x1 * x1, 3) + small_multiple(self.a * x1, 2) + one_like(x1);
+                numerator / small_multiple(self.b * y1, 2)
+            }
+            false => (y2 - y1) / (x2 - x1),
+        };
+        let x3 = self.b * slope * slope - self.a - x1 - x2;
+        MontgomeryPoint {
+            coordinates: Coordinates::Affine {
+                x: x3,
+                y: slope * (x1 - x3) - y1,
+            },
+            ..self
+        }
+    }
+}
+
+impl<T> Neg for MontgomeryPoint<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let coordinates = match self.coordinates {
+            Coordinates::Affine { x, y } => Coordinates::Affine { x, y: -y },
+            Coordinates::Identity => Coordinates::Identity,
+        };
+        MontgomeryPoint {
+            coordinates,
+            ..self
+        }
+    }
+}
+
+impl<T> Sub for MontgomeryPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialEq
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+#[cfg(test)]
+mod test_montgomery {
+    use super::*;
+
+    fn curve(p: i64, a: i64, b: i64) -> MontgomeryCurve<FiniteBody<i64>> {
+        MontgomeryCurve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap()
+    }
+
+    // x(2P) = (x^2 - 1)^2 / (4x(x^2 + Ax + 1)), la duplicacion en afines
+    fn double(curve: &MontgomeryCurve<FiniteBody<i64>>, x: FiniteBody<i64>) -> FiniteBody<i64> {
+        let one = FiniteBody::new(x.p, 1);
+        let numerator = (x * x - one) * (x * x - one);
+        let denominator = small_multiple(x * (x * x + curve.a * x + one), 4);
+        numerator / denominator
+    }
+
+    #[test]
+    fn test_01_singular_curves_are_rejected() {
+        let p = 101;
+        assert_eq!(
+            MontgomeryCurve::new(FiniteBody::new(p, 2), FiniteBody::new(p, 1)),
+            Err(EllipticCurveError::SingularCurve)
+        );
+        assert_eq!(
+            MontgomeryCurve::new(FiniteBody::new(p, -2), FiniteBody::new(p, 1)),
+            Err(EllipticCurveError::SingularCurve)
+        );
+        assert_eq!(
+            MontgomeryCurve::new(FiniteBody::new(p, 3), FiniteBody::new(p, 0)),
+            Err(EllipticCurveError::SingularCurve)
+        );
+        assert!(MontgomeryCurve::new(FiniteBody::new(p, 3), FiniteBody::new(p, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_02_small_multiples() {
+        let curve = curve(1019, 6, 1);
+        let x = FiniteBody::new(1019, 5);
+        assert_eq!(curve.ladder(0, x, 8), FiniteBody::new(1019, 0));
+        assert_eq!(curve.ladder(1, x, 8), x);
+        assert_eq!(curve.ladder(-1, x, 8), x);
+        assert_eq!(curve.ladder(2, x, 8), double(&curve, x));
+        assert_eq!(curve.ladder(4, x, 8), double(&curve, double(&curve, x)));
+    }
+
+    #[test]
+    fn test_03_ladder_composes() {
+        // k1 * (k2 * P) = (k1 * k2) * P, tambien si x es de un punto de la torcedura
+        let curve = curve(1019, 6, 1);
+        for x in [3, 5, 17, 400] {
+            let x = FiniteBody::new(1019, x);
+            for (k1, k2) in [(3_u32, 7_u32), (12, 5), (33, 31)] {
+                let composed = curve.ladder(k1, curve.ladder(k2, x, 16), 16);
+                assert_eq!(composed, curve.ladder(k1 * k2, x, 16));
+            }
+        }
+    }
+
+    #[test]
+    fn test_04_extra_bits_do_not_change_the_result() {
+        let curve = curve(1019, 6, 1);
+        let x = FiniteBody::new(1019, 17);
+        let expected = curve.ladder(45, x, 6);
+        for bits in [7, 10, 64] {
+            assert_eq!(curve.ladder(45, x, bits), expected);
+        }
+    }
+
+    #[test]
+    fn test_05_points_agree_with_the_ladder() {
+        let curve = curve(1019, 6, 1);
+        let one = FiniteBody::new(1019, 1);
+        let mut x = FiniteBody::new(1019, 2);
+        let y = loop {
+            match (x * x * x + curve.a * x * x + x).sqrt() {
+                Some(y) if y.value != 0 => break y,
+                _ => x = x + one,
+            }
+        };
+        let point = curve.point(x, y).unwrap();
+        assert!(curve.contains(&point));
+        assert_eq!(curve.point(x, y + one), None);
+
+        for k in 1..40 {
+            let multiple = point.mul_scalar(k);
+            let expected = multiple
+                .coordinates()
+                .map_or(FiniteBody::new(1019, 0), |(x, _)| x);
+            assert_eq!(curve.ladder(k, x, 8), expected);
+            assert_eq!(point.mul_scalar(-k), -multiple);
+        }
+        assert!((point - point).is_identity());
+        assert_eq!(point + curve.identity(), point);
+    }
+}
@@ -0,0 +1,330 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{jacobian_point::JacobianPoint, naf::wnaf, point_elliptic_curve::PointEllipticCurve};
+
+/// Hasta esta cantidad de terminos se usa Straus, a partir de ahi Pippenger.
+const STRAUS_MAX_TERMS: usize = 16;
+/// Ventana de la wNAF de cada termino en Straus.
+const STRAUS_WINDOW: usize = 4;
+
+/// k1 * P1 + k2 * P2 + ... + kn * Pn (multi-scalar multiplication). Elige el metodo segun la
+/// cantidad de terminos: `straus` para pocos y `pippenger` para muchos.
+///
+/// None si no hay terminos (no hay curva para el punto del infinito) o si los puntos no estan todos
+/// en la misma curva, igual que la suma de puntos.
+pub fn msm<X, K>(
+    terms: &[(K, PointEllipticCurve<FiniteBody<X>>)],
+) -> Option<PointEllipticCurve<FiniteBody<X>>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    K: Into<BigInteger> + Copy,
+{
+    match terms.len() <= STRAUS_MAX_TERMS {
+        true => straus(terms),
+        false => pippenger(terms),
+    }
+}
+
+/// Metodo de Straus (el "truco de Shamir" para dos terminos): se recodifica cada escalar en wNAF y
+/// se recorren todos a la vez, compartiendo las duplicaciones. Cuesta bits(k) duplicaciones en total
+/// en lugar de bits(k) por termino.
+pub fn straus<X, K>(
+    terms: &[(K, PointEllipticCurve<FiniteBody<X>>)],
+) -> Option<PointEllipticCurve<FiniteBody<X>>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    K: Into<BigInteger> + Copy,
+{
+    let terms = signed_terms(terms)?;
+    let (a, b) = (terms[0].1.a, terms[0].1.b);
+
+    let recoded: Vec<(Vec<i8>, Vec<_>)> = terms
+        .iter()
+        .map(|(k, point)| {
+            (
+                wnaf(k, STRAUS_WINDOW),
+                point.odd_multiples(1 << (STRAUS_WINDOW - 2)),
+            )
+        })
+        .collect();
+    let len = recoded.iter().map(|(digits, _)| digits.len()).max()?;
+
+    let mut result = JacobianPoint::identity(a, b);
+    for i in (0..len).rev() {
+        result = result.double();
+        for (digits, table) in &recoded {
+            match digits.get(i) {
+                None | Some(0) => {}
+                Some(d) if *d > 0 => result = result.add_mixed(&table[(*d as usize) / 2]),
+                Some(d) => result = result.add_mixed(&-table[(-*d as usize) / 2]),
+            }
+        }
+    }
+    Some(result.to_affine())
+}
+
+/// Metodo de Pippenger (buckets): se cortan los escalares en ventanas de c bits y, en cada ventana,
+/// se acumula cada punto en el bucket de su digito. La suma sum j * B_j se hace con sumas parciales
+/// (2^c sumas) en lugar de multiplicar cada bucket. Para n terminos cuesta del orden de
+/// bits(k) / c * (n + 2^c) sumas, mucho menos que n * bits(k) cuando n es grande.
+pub fn pippenger<X, K>(
+    terms: &[(K, PointEllipticCurve<FiniteBody<X>>)],
+) -> Option<PointEllipticCurve<FiniteBody<X>>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    K: Into<BigInteger> + Copy,
+{
+    let terms = signed_terms(terms)?;
+    let (a, b) = (terms[0].1.a, terms[0].1.b);
+    let identity = JacobianPoint::identity(a, b);
+
+    let c = bucket_window(terms.len());
+    let bits = terms.iter().map(|(k, _)| k.bits()).max()?;
+    let windows = bits.div_ceil(c);
+
+    let mut result = identity;
+    for window in (0..windows).rev() {
+        for _ in 0..c {
+            result = result.double();
+        }
+
+        let mut buckets = vec![identity; (1 << c) - 1];
+        for (k, point) in &terms {
+            let digit = (0..c).fold(0, |acc, bit| {
+                acc | (k.bit(window * c + bit) as usize) << bit
+            });
+            if digit > 0 {
+                buckets[digit - 1] = buckets[digit - 1].add_mixed(point);
+            }
+        }
+
+        // sum j * B_j = B_m + (B_m + B_{m-1}) + ... + (B_m + ... + B_1)
+        let mut running = identity;
+        let mut window_sum = identity;
+        for bucket in buckets.iter().rev() {
+            running = running.add_jacobian(bucket);
+            window_sum = window_sum.add_jacobian(&running);
+        }
+        result = result.add_jacobian(&window_sum);
+    }
+    Some(result.to_affine())
+}
+
+// c ~ log2(n) equilibra las sumas a los buckets (n por ventana) con las de las sumas parciales (2^c)
+fn bucket_window(terms: usize) -> usize {
+    (terms.ilog2() as usize).clamp(2, 16)
+}
+
+// Escalares como |k| y puntos como sign(k) * P. None si no hay terminos o si hay puntos de curvas
+// distintas.
+#[allow(clippy::type_complexity)]
+fn signed_terms<T, K>(
+    terms: &[(K, PointEllipticCurve<T>)],
+) -> Option<Vec<(BigInteger, PointEllipticCurve<T>)>>
+where
+    T: Neg<Output = T> + PartialEq + Copy,
+    K: Into<BigInteger> + Copy,
+{
+    let (_, first) = terms.first()?;
+    if terms
+        .iter()
+        .any(|(_, point)| point.a != first.a || point.b != first.b)
+    {
+        return None;
+    }
+    Some(
+        terms
+            .iter()
+            .map(|(k, point)| {
+                let k: BigInteger = (*k).into();
+                match k.is_negative() {
+                    true => (k.abs(), -*point),
+                    false => (k, *point),
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test_msm {
+    use rand::Rng;
+
+    use crate::{
+        elliptic_curve::{
+            named_curves::{SECP256K1, TP1_P1021, TP1_P223},
+            operation_count::count_operations,
+        },
+        finite_body::finite_body::FiniteBody,
+    };
+
+    use super::*;
+
+    fn naive_sum<T, K>(terms: &[(K, PointEllipticCurve<T>)]) -> PointEllipticCurve<T>
+    where
+        T: Add<T>
+            + Div<Output = T>
+            + Sub<Output = T>
+            + Neg<Output = T>
+            + Mul<isize, Output = T>
+            + Pow<usize, Output = T>
+            + PartialOrd
+            + Copy
+            + Zero
+            + One,
+        K: Into<BigInteger> + Copy,
+    {
+        let (a, b) = (terms[0].1.a, terms[0].1.b);
+        terms
+            .iter()
+            .fold(PointEllipticCurve::new_inf(a, b), |acc, (k, point)| {
+                (acc + point.mul_scalar(*k)).unwrap()
+            })
+    }
+
+    fn random_terms(count: usize) -> Vec<(i64, PointEllipticCurve<FiniteBody<i32>>)> {
+        let g = TP1_P1021.domain_parameters::<i32>().unwrap().g;
+        let mut rng = rand::thread_rng();
+        (0..count)
+            .map(|_| (rng.gen_range(-5000..5000), g * rng.gen_range(0..1039_usize)))
+            .collect()
+    }
+
+    #[test]
+    fn test_01_shamir_two_terms() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let q = params.g * 200;
+        for (a, b) in [(655_i32, 17_i32), (0, 5), (-3, 1038), (1039, -1039), (0, 0)] {
+            let expected = (params.g * a + q * b).unwrap();
+            assert_eq!(msm(&[(a, params.g), (b, q)]).unwrap(), expected);
+            assert_eq!(straus(&[(a, params.g), (b, q)]).unwrap(), expected);
+            assert_eq!(pippenger(&[(a, params.g), (b, q)]).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_02_random_terms_against_naive_sum() {
+        for count in [1, 3, 10, 16, 17, 40, 150] {
+            let terms = random_terms(count);
+            let expected = naive_sum(&terms);
+            assert_eq!(msm(&terms).unwrap(), expected, "{} terminos", count);
+            assert_eq!(straus(&terms).unwrap(), expected, "{} terminos", count);
+            assert_eq!(pippenger(&terms).unwrap(), expected, "{} terminos", count);
+        }
+    }
+
+    #[test]
+    fn test_03_points_of_different_order() {
+        // y^2 = x^3 + 7 sobre F_223 tiene puntos de orden 1, 3, 7, 21, ...
+        let params = TP1_P223.domain_parameters::<i32>().unwrap();
+        let points: Vec<_> = (0..223)
+            .filter_map(|x| {
+                (0..223).find_map(|y| {
+                    params
+                        .curve
+                        .point(FiniteBody::new(223, x), FiniteBody::new(223, y))
+                })
+            })
+            .collect();
+        let terms: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i as i32 * 37 - 500, *p))
+            .collect();
+        assert_eq!(msm(&terms).unwrap(), naive_sum(&terms));
+        assert_eq!(straus(&terms[..5]).unwrap(), naive_sum(&terms[..5]));
+    }
+
+    #[test]
+    fn test_04_big_scalars() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let q = params.g * BigInteger::from(123456789);
+        let a = params.n - BigInteger::from(3);
+        let b = params.n >> 1;
+        let expected = (params.g * a + q * b).unwrap();
+        assert_eq!(msm(&[(a, params.g), (b, q)]).unwrap(), expected);
+
+        // Shamir: una sola cadena de duplicaciones para los dos terminos
+        let (_, count) = count_operations(|| msm(&[(a, params.g), (b, q)]));
+        assert!(count.doublings < 260 + 2);
+    }
+
+    #[test]
+    fn test_05_empty_and_mixed_curves() {
+        let terms: [(i32, PointEllipticCurve<FiniteBody<i32>>); 0] = [];
+        assert!(msm(&terms).is_none());
+
+        let g1021 = TP1_P1021.domain_parameters::<i32>().unwrap().g;
+        let g223 = TP1_P223.domain_parameters::<i32>().unwrap().g;
+        assert!(msm(&[(1, g1021), (1, g223)]).is_none());
+    }
+
+    #[test]
+    fn test_06_pippenger_needs_fewer_additions() {
+        let terms = random_terms(200);
+        let (_, straus_count) = count_operations(|| straus(&terms));
+        let (_, pippenger_count) = count_operations(|| pippenger(&terms));
+        assert!(pippenger_count.additions < straus_count.additions);
+    }
+}
@@ -0,0 +1,211 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{jacobian_point::JacobianPoint, point_elliptic_curve::PointEllipticCurve};
+
+/// Forma no adyacente (NAF) de |k|: digitos en {-1, 0, 1}, del menos significativo al mas
+/// significativo, sin dos digitos distintos de cero seguidos. Es la wNAF con w = 2.
+pub fn naf(k: &BigInteger) -> Vec<i8> {
+    wnaf(k, 2)
+}
+
+/// wNAF de |k| con ventana `w` (2 <= w <= 8): sum d_i 2^i = |k|, cada digito es cero o impar con
+/// |d_i| < 2^(w-1), y entre dos digitos distintos de cero hay al menos w - 1 ceros. Los digitos van
+/// del menos significativo al mas significativo.
+pub fn wnaf(k: &BigInteger, w: usize) -> Vec<i8> {
+    assert!((2..=8).contains(&w), "the window must be between 2 and 8");
+    let modulus = 1_i16 << w;
+
+    let mut k = k.abs();
+    let mut digits = Vec::with_capacity(k.bits() + 1);
+    while !k.is_zero() {
+        let digit = match k.is_odd() {
+            true => {
+                // k mod 2^w, llevado a (-2^(w-1), 2^(w-1))
+                let low = (0..w).fold(0_i16, |acc, i| acc | (k.bit(i) as i16) << i);
+                let digit = if low >= modulus / 2 {
+                    low - modulus
+                } else {
+                    low
+                };
+                k = k - BigInteger::from(digit);
+                digit as i8
+            }
+            false => 0,
+        };
+        digits.push(digit);
+        k = k >> 1;
+    }
+    digits
+}
+
+impl<X> PointEllipticCurve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// [P, 3P, 5P, ..., (2 * count - 1)P], en coordenadas afines (una sola inversion para todos).
+    pub fn odd_multiples(&self, count: usize) -> Vec<Self> {
+        let point = JacobianPoint::from_affine(self);
+        let double = point.double();
+        let mut multiples = Vec::with_capacity(count);
+        let mut acum = point;
+        for _ in 0..count {
+            multiples.push(acum);
+            acum = acum.add_jacobian(&double);
+        }
+        JacobianPoint::batch_normalize(&multiples)
+    }
+
+    /// k * P recodificando k en wNAF con ventana `w` (2 <= w <= 8). Se precalculan los 2^(w-2)
+    /// multiplos impares P, 3P, ..., (2^(w-1) - 1)P y despues hay una duplicacion por bit y una
+    /// suma cada w + 1 bits en promedio, contra una cada 2 bits de "double and add". Con w = 2 es el
+    /// metodo NAF, que no precalcula nada.
+    pub fn mul_wnaf<K: Into<BigInteger>>(&self, k: K, w: usize) -> Self {
+        let k = k.into();
+        let digits = wnaf(&k, w);
+        let table = self.odd_multiples(1 << (w - 2));
+
+        let mut result = JacobianPoint::identity(self.a, self.b);
+        for digit in digits.iter().rev() {
+            result = result.double();
+            match digit {
+                0 => {}
+                d if *d > 0 => result = result.add_mixed(&table[(*d as usize) / 2]),
+                d => result = result.add_mixed(&-table[(-*d as usize) / 2]),
+            }
+        }
+
+        let result = result.to_affine();
+        match k.is_negative() {
+            true => -result,
+            false => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_naf {
+    use num::Num;
+    use rand::Rng;
+
+    use crate::elliptic_curve::{
+        named_curves::{SECP256K1, TP1_P1021},
+        operation_count::count_operations,
+    };
+
+    use super::*;
+
+    fn evaluate(digits: &[i8]) -> BigInteger {
+        digits.iter().rev().fold(BigInteger::zero(), |acc, d| {
+            (acc << 1) + BigInteger::from(*d)
+        })
+    }
+
+    #[test]
+    fn test_01_naf() {
+        // 7 = 8 - 1
+        assert_eq!(naf(&BigInteger::from(7)), vec![-1, 0, 0, 1]);
+        assert_eq!(naf(&BigInteger::from(0)), Vec::<i8>::new());
+
+        for k in 0..2000 {
+            let digits = naf(&BigInteger::from(k));
+            assert_eq!(evaluate(&digits), BigInteger::from(k));
+            assert!(digits.windows(2).all(|pair| pair[0] == 0 || pair[1] == 0));
+        }
+    }
+
+    #[test]
+    fn test_02_wnaf_properties() {
+        let mut rng = rand::thread_rng();
+        for w in 2..=8 {
+            for _ in 0..50 {
+                let k = BigInteger::from(rng.gen::<u64>()) * BigInteger::from(rng.gen::<u64>());
+                let digits = wnaf(&k, w);
+                assert_eq!(evaluate(&digits), k);
+                assert!(*digits.last().unwrap() > 0);
+
+                let non_zero: Vec<_> = (0..digits.len()).filter(|i| digits[*i] != 0).collect();
+                for i in &non_zero {
+                    assert!(digits[*i] % 2 != 0 && (digits[*i] as i16).abs() < 1 << (w - 1));
+                }
+                assert!(non_zero.windows(2).all(|pair| pair[1] - pair[0] >= w));
+            }
+        }
+    }
+
+    #[test]
+    fn test_03_odd_multiples() {
+        let g = TP1_P1021.domain_parameters::<i32>().unwrap().g;
+        let multiples = g.odd_multiples(8);
+        for (i, multiple) in multiples.iter().enumerate() {
+            assert_eq!(*multiple, g * (2 * i + 1));
+        }
+    }
+
+    #[test]
+    fn test_04_mul_wnaf_matches_double_and_add() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        for w in 2..=6 {
+            for k in -1100..1100 {
+                assert_eq!(
+                    params.g.mul_wnaf(k, w),
+                    params.g * k,
+                    "k = {}, w = {}",
+                    k,
+                    w
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_05_mul_wnaf_big_scalar() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let k = BigInteger::from_str_radix(
+            "d3b9c9d9a754ac3e9f3344d507b07fa39c6ab7104a08c720cede24428a013fda",
+            16,
+        )
+        .unwrap();
+        let expected = params.g * k;
+        for w in [2, 4, 5, 8] {
+            assert_eq!(params.g.mul_wnaf(k, w), expected);
+        }
+    }
+
+    #[test]
+    fn test_06_wnaf_needs_fewer_additions() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let k = params.n - BigInteger::from(12345);
+
+        let (_, double_and_add) = count_operations(|| params.g.mul_scalar(k));
+        let (_, naf) = count_operations(|| params.g.mul_wnaf(k, 2));
+        let (_, wnaf) = count_operations(|| params.g.mul_wnaf(k, 5));
+
+        assert!(naf.additions < double_and_add.additions);
+        // las sumas de la tabla (8 multiplos) estan incluidas
+        assert!(wnaf.additions < naf.additions);
+        assert!(double_and_add.doublings.abs_diff(wnaf.doublings) <= 2);
+    }
+}
@@ -0,0 +1,456 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, Num, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{curve::Curve, point_elliptic_curve::PointEllipticCurve};
+
+/// Parametros de dominio de una curva: el primo p del cuerpo, la curva, el generador G, el orden n de G
+/// y el cofactor h = #E(F_p) / n.
+#[derive(Debug, Clone, Copy)]
+pub struct DomainParameters<T> {
+    pub p: T,
+    pub curve: Curve<FiniteBody<T>>,
+    pub g: PointEllipticCurve<FiniteBody<T>>,
+    pub n: T,
+    pub h: T,
+}
+
+/// Entrada del registro de curvas con nombre. Los valores se guardan como texto, en hexadecimal con
+/// prefijo "0x" (tal como se publican) o en decimal, y se convierten con `domain_parameters`.
+#[derive(Debug)]
+pub struct NamedCurve {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub oid: Option<&'static str>,
+    p: &'static str,
+    a: &'static str,
+    b: &'static str,
+    gx: &'static str,
+    gy: &'static str,
+    n: &'static str,
+    h: &'static str,
+}
+
+/// secp256k1 (SEC 2).
+pub const SECP256K1: NamedCurve = NamedCurve {
+    name: "secp256k1",
+    aliases: &[],
+    oid: Some("1.3.132.0.10"),
+    p: "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    a: "0x0",
+    b: "0x7",
+    gx: "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    gy: "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    n: "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    h: "0x1",
+};
+
+/// secp256r1 (SEC 2 / FIPS 186-4).
+pub const SECP256R1: NamedCurve = NamedCurve {
+    name: "secp256r1",
+    aliases: &["P-256", "prime256v1"],
+    oid: Some("1.2.840.10045.3.1.7"),
+    p: "0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    a: "0xffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+    b: "0x5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+    gx: "0x6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    gy: "0x4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+    n: "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+    h: "0x1",
+};
+
+/// secp224r1 (SEC 2 / FIPS 186-4).
+pub const SECP224R1: NamedCurve = NamedCurve {
+    name: "secp224r1",
+    aliases: &["P-224"],
+    oid: Some("1.3.132.0.33"),
+    p: "0xffffffffffffffffffffffffffffffff000000000000000000000001",
+    a: "0xfffffffffffffffffffffffffffffffefffffffffffffffffffffffe",
+    b: "0xb4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4",
+    gx: "0xb70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21",
+    gy: "0xbd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34",
+    n: "0xffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d",
+    h: "0x1",
+};
+
+/// secp384r1 (SEC 2 / FIPS 186-4).
+pub const SECP384R1: NamedCurve = NamedCurve {
+    name: "secp384r1",
+    aliases: &["P-384"],
+    oid: Some("1.3.132.0.34"),
+    p: "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff",
+    a: "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000fffffffc",
+    b: "0xb3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+    gx: "0xaa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+    gy: "0x3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+    n: "0xffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+    h: "0x1",
+};
+
+/// secp521r1 (SEC 2 / FIPS 186-4).
+pub const SECP521R1: NamedCurve = NamedCurve {
+    name: "secp521r1",
+    aliases: &["P-521"],
+    oid: Some("1.3.132.0.35"),
+    p: "0x1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+    a: "0x1fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc",
+    b: "0x51953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00",
+    gx: "0xc6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66",
+    gy: "0x11839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650",
+    n: "0x1fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409",
+    h: "0x1",
+};
+
+/// secp192k1 (SEC 2).
+pub const SECP192K1: NamedCurve = NamedCurve {
+    name: "secp192k1",
+    aliases: &[],
+    oid: Some("1.3.132.0.31"),
+    p: "0xfffffffffffffffffffffffffffffffffffffffeffffee37",
+    a: "0x0",
+    b: "0x3",
+    gx: "0xdb4ff10ec057e9ae26b07d0280b7f4341da5d1b1eae06c7d",
+    gy: "0x9b2f2f6d9c5628a7844163d015be86344082aa88d95e2f9d",
+    n: "0xfffffffffffffffffffffffe26f2fc170f69466a74defd8d",
+    h: "0x1",
+};
+
+/// brainpoolP256r1 (RFC 5639).
+pub const BRAINPOOL_P256R1: NamedCurve = NamedCurve {
+    name: "brainpoolP256r1",
+    aliases: &[],
+    oid: Some("1.3.36.3.3.2.8.1.1.7"),
+    p: "0xa9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377",
+    a: "0x7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9",
+    b: "0x26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6",
+    gx: "0x8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262",
+    gy: "0x547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997",
+    n: "0xa9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7",
+    h: "0x1",
+};
+
+/// Curva del item 3 del TP: y^2 = x^3 + 6 sobre F_43 con G1 = (13, 15).
+pub const TP1_P43: NamedCurve = NamedCurve {
+    name: "tp1-p43",
+    aliases: &[],
+    oid: None,
+    p: "43",
+    a: "0",
+    b: "6",
+    gx: "13",
+    gy: "15",
+    n: "13",
+    h: "3",
+};
+
+/// Curva de "Programming Bitcoin": y^2 = x^3 + 7 sobre F_223 con G = (47, 71).
+pub const TP1_P223: NamedCurve = NamedCurve {
+    name: "tp1-p223",
+    aliases: &[],
+    oid: None,
+    p: "223",
+    a: "0",
+    b: "7",
+    gx: "47",
+    gy: "71",
+    n: "21",
+    h: "12",
+};
+
+/// Curva del item 2 del TP: y^2 = x^3 - 3x - 3 sobre F_1021 con P = (379, 1011).
+pub const TP1_P1021: NamedCurve = NamedCurve {
+    name: "tp1-p1021",
+    aliases: &[],
+    oid: None,
+    p: "1021",
+    a: "-3",
+    b: "-3",
+    gx: "379",
+    gy: "1011",
+    n: "1039",
+    h: "1",
+};
+
+/// Curva del item 4 del TP: y^2 = x^3 + 905x + 100 sobre F_1021 con G = (1006, 416).
+pub const TP1_P1021_DLP: NamedCurve = NamedCurve {
+    name: "tp1-p1021-dlp",
+    aliases: &[],
+    oid: None,
+    p: "1021",
+    a: "905",
+    b: "100",
+    gx: "1006",
+    gy: "416",
+    n: "966",
+    h: "1",
+};
+
+pub const NAMED_CURVES: [&NamedCurve; 11] = [
+    &SECP256K1,
+    &SECP256R1,
+    &SECP224R1,
+    &SECP384R1,
+    &SECP521R1,
+    &SECP192K1,
+    &BRAINPOOL_P256R1,
+    &TP1_P43,
+    &TP1_P223,
+    &TP1_P1021,
+    &TP1_P1021_DLP,
+];
+
+pub(crate) fn parse(value: &str) -> BigInteger {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => BigInteger::from_str_radix(hex, 16),
+        None => BigInteger::from_str_radix(value, 10),
+    };
+    parsed.expect("invalid constant in the named curves registry")
+}
+
+impl NamedCurve {
+    /// Busca por nombre o alias, sin distinguir mayusculas.
+    pub fn by_name(name: &str) -> Option<&'static NamedCurve> {
+        NAMED_CURVES.into_iter().find(|curve| {
+            curve.name.eq_ignore_ascii_case(name)
+                || curve
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Busca por OID en notacion de puntos, por ejemplo "1.3.132.0.10".
+    pub fn by_oid(oid: &str) -> Option<&'static NamedCurve> {
+        NAMED_CURVES
+            .into_iter()
+            .find(|curve| curve.oid == Some(oid))
+    }
+
+    /// Parametros de dominio con valores de tipo `T`. Devuelve None si algun valor no entra en `T`
+    /// (por ejemplo secp256k1 con `i32`), para las curvas del TP alcanza con `i32`.
+    pub fn domain_parameters<T>(&self) -> Option<DomainParameters<T>>
+    where
+        T: Add<T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Sub<Output = T>
+            + Neg<Output = T>
+            + Rem<Output = T>
+            + Pow<usize, Output = T>
+            + PartialOrd
+            + Copy
+            + Zero
+            + One
+            + TryFrom<BigInteger>,
+    {
+        let convert = |value: &str| T::try_from(parse(value)).ok();
+        let p = convert(self.p)?;
+        let curve = Curve::new(
+            FiniteBody::new(p, convert(self.a)?),
+            FiniteBody::new(p, convert(self.b)?),
+        )
+        .ok()?;
+        let g = curve.point(
+            FiniteBody::new(p, convert(self.gx)?),
+            FiniteBody::new(p, convert(self.gy)?),
+        )?;
+
+        Some(DomainParameters {
+            p,
+            curve,
+            g,
+            n: convert(self.n)?,
+            h: convert(self.h)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_named_curves {
+    use super::*;
+
+    fn assert_kg(named_curve: &NamedCurve, k: usize, x: &str, y: &str) {
+        let params = named_curve.domain_parameters::<BigInteger>().unwrap();
+        let expected = params
+            .curve
+            .point(
+                FiniteBody::new(params.p, BigInteger::from_str_radix(x, 16).unwrap()),
+                FiniteBody::new(params.p, BigInteger::from_str_radix(y, 16).unwrap()),
+            )
+            .unwrap();
+
+        assert_eq!(params.g * k, expected, "{} * G en {}", k, named_curve.name);
+    }
+
+    #[test]
+    fn test_01_lookup_by_name_and_alias() {
+        assert_eq!(NamedCurve::by_name("secp256k1").unwrap().name, "secp256k1");
+        assert_eq!(NamedCurve::by_name("P-256").unwrap().name, "secp256r1");
+        assert_eq!(NamedCurve::by_name("prime256v1").unwrap().name, "secp256r1");
+        assert_eq!(NamedCurve::by_name("p-521").unwrap().name, "secp521r1");
+        assert_eq!(NamedCurve::by_name("tp1-p43").unwrap().name, "tp1-p43");
+        assert!(NamedCurve::by_name("curve25519").is_none());
+    }
+
+    #[test]
+    fn test_02_lookup_by_oid() {
+        assert_eq!(
+            NamedCurve::by_oid("1.3.132.0.10").unwrap().name,
+            "secp256k1"
+        );
+        assert_eq!(
+            NamedCurve::by_oid("1.2.840.10045.3.1.7").unwrap().name,
+            "secp256r1"
+        );
+        assert_eq!(
+            NamedCurve::by_oid("1.3.36.3.3.2.8.1.1.7").unwrap().name,
+            "brainpoolP256r1"
+        );
+        assert!(NamedCurve::by_oid("1.2.3").is_none());
+    }
+
+    #[test]
+    fn test_03_all_generators_are_on_their_curve() {
+        for named_curve in NAMED_CURVES {
+            assert!(
+                named_curve.domain_parameters::<BigInteger>().is_some(),
+                "{}",
+                named_curve.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_04_course_curves_fit_in_i32() {
+        let params = TP1_P43.domain_parameters::<i32>().unwrap();
+        assert_eq!(params.g, (FiniteBody::new(43, 13), FiniteBody::new(43, 15)));
+        assert_eq!(params.g * params.n as usize, params.curve.identity());
+
+        assert!(SECP256K1.domain_parameters::<i32>().is_none());
+    }
+
+    #[test]
+    fn test_05_course_curves_orders() {
+        for named_curve in [&TP1_P43, &TP1_P223, &TP1_P1021, &TP1_P1021_DLP] {
+            let params = named_curve.domain_parameters::<i32>().unwrap();
+            let n = params.n as usize;
+            assert_eq!(params.g * n, params.curve.identity());
+            for k in 1..n {
+                assert_ne!(params.g * k, params.curve.identity());
+            }
+        }
+    }
+
+    #[test]
+    fn test_06_course_curve_kat() {
+        // kP del item 2 del TP
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        assert_eq!(
+            params.g * 655,
+            (FiniteBody::new(1021, 388), FiniteBody::new(1021, 60))
+        );
+    }
+
+    #[test]
+    fn test_07_known_answers_k_g() {
+        // valores calculados con OpenSSL
+        assert_kg(
+            &SECP256K1,
+            2,
+            "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+            "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+        );
+        assert_kg(
+            &SECP256K1,
+            3,
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+        );
+        assert_kg(
+            &SECP256K1,
+            20,
+            "4ce119c96e2fa357200b559b2f7dd5a5f02d5290aff74b03f3e471b273211c97",
+            "12ba26dcb10ec1625da61fa10a844c676162948271d96967450288ee9233dc3a",
+        );
+        assert_kg(
+            &SECP256R1,
+            2,
+            "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+            "7775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1",
+        );
+        assert_kg(
+            &SECP256R1,
+            3,
+            "5ecbe4d1a6330a44c8f7ef951d4bf165e6c6b721efada985fb41661bc6e7fd6c",
+            "8734640c4998ff7e374b06ce1a64a2ecd82ab036384fb83d9a79b127a27d5032",
+        );
+        assert_kg(
+            &SECP256R1,
+            20,
+            "83a01a9378395bab9bcd6a0ad03cc56d56e6b19250465a94a234dc4c6b28da9a",
+            "76e49b6de2f73234ae6a5eb9d612b75c9f2202bb6923f54ff8240aaa86f640b8",
+        );
+        assert_kg(
+            &SECP224R1,
+            2,
+            "706a46dc76dcb76798e60e6d89474788d16dc18032d268fd1a704fa6",
+            "1c2b76a7bc25e7702a704fa986892849fca629487acf3709d2e4e8bb",
+        );
+        assert_kg(
+            &SECP224R1,
+            3,
+            "df1b1d66a551d0d31eff822558b9d2cc75c2180279fe0d08fd896d04",
+            "a3f7f03cadd0be444c0aa56830130ddf77d317344e1af3591981a925",
+        );
+        assert_kg(
+            &SECP224R1,
+            20,
+            "fcc7f2b45df1cd5a3c0c0731ca47a8af75cfb0347e8354eefe782455",
+            "d5d7110274cba7cdee90e1a8b0d394c376a5573db6be0bf2747f530",
+        );
+        assert_kg(&SECP384R1, 2, "8d999057ba3d2d969260045c55b97f089025959a6f434d651d207d19fb96e9e4fe0e86ebe0e64f85b96a9c75295df61", "8e80f1fa5b1b3cedb7bfe8dffd6dba74b275d875bc6cc43e904e505f256ab4255ffd43e94d39e22d61501e700a940e80");
+        assert_kg(&SECP384R1, 3, "77a41d4606ffa1464793c7e5fdc7d98cb9d3910202dcd06bea4f240d3566da6b408bbae5026580d02d7e5c70500c831", "c995f7ca0b0c42837d0bbe9602a9fc998520b41c85115aa5f7684c0edc111eacc24abd6be4b5d298b65f28600a2f1df1");
+        assert_kg(&SECP384R1, 20, "605508ec02c534bceee9484c86086d2139849e2b11c1a9ca1e2808dec2eaf161ac8a105d70d4f85c50599be5800a623f", "5158ee87962ac6b81f00a103b8543a07381b7639a3a65f1353aef11b733106dde92e99b78de367b48e238c38dad8eedd");
+        assert_kg(&SECP521R1, 2, "433c219024277e7e682fcb288148c282747403279b1ccc06352c6e5505d769be97b3b204da6ef55507aa104a3a35c5af41cf2fa364d60fd967f43e3933ba6d783d", "f4bb8cc7f86db26700a7f3eceeeed3f0b5c6b5107c4da97740ab21a29906c42dbbb3e377de9f251f6b93937fa99a3248f4eafcbe95edc0f4f71be356d661f41b02");
+        assert_kg(&SECP521R1, 3, "1a73d352443de29195dd91d6a64b5959479b52a6e5b123d9ab9e5ad7a112d7a8dd1ad3f164a3a4832051da6bd16b59fe21baeb490862c32ea05a5919d2ede37ad7d", "13e9b03b97dfa62ddd9979f86c6cab814f2f1557fa82a9d0317d2f8ab1fa355ceec2e2dd4cf8dc575b02d5aced1dec3c70cf105c9bc93a590425f588ca1ee86c0e5");
+        assert_kg(&SECP521R1, 20, "18bdd7f1b889598a4653deeae39cc6f8cc2bd767c2ab0d93fb12e968fbed342b51709506339cb1049cb11dd48b9bdb3cd5cad792e43b74e16d8e2603bfb11b0344f", "c5aadbe63f68ca5b6b6908296959bf0af89ee7f52b410b9444546c550952d311204da3bdddc6d4eae7edfaec1030da8ef837ccb22eee9cfc94dd3287fed0990f94");
+        assert_kg(
+            &SECP192K1,
+            2,
+            "f091cf6331b1747684f5d2549cd1d4b3a8bed93b94f93cb6",
+            "fd7af42e1e7565a02e6268661c5e42e603da2d98a18f2ed5",
+        );
+        assert_kg(
+            &SECP192K1,
+            3,
+            "6e43b7dcae2fd5e0bf2a1ba7615ca3b9065487c9a67b4583",
+            "c48dcea47ae08e84d5fedc3d09e4c19606a290f7a19a6a58",
+        );
+        assert_kg(
+            &SECP192K1,
+            20,
+            "9c98bfeee470b7095592668d77665b4c0b71ad5d4b7d7fe9",
+            "b7b807acf5c1f753796a1982eca001c8772bc43c9987bf45",
+        );
+        assert_kg(
+            &BRAINPOOL_P256R1,
+            2,
+            "743cf1b8b5cd4f2eb55f8aa369593ac436ef044166699e37d51a14c2ce13ea0e",
+            "36ed163337deba9c946fe0bb776529da38df059f69249406892ada097eeb7cd4",
+        );
+        assert_kg(
+            &BRAINPOOL_P256R1,
+            3,
+            "a8f217b77338f1d4d6624c3ab4f6cc16d2aa843d0c0fca016b91e2ad25cae39d",
+            "4b49cafc7dac26bb0aa2a6850a1b40f5fac10e4589348fb77e65cc5602b74f9d",
+        );
+        assert_kg(
+            &BRAINPOOL_P256R1,
+            20,
+            "eca4be77f21e1ec417f28ff285452ec726f66afa4739cc4ea40fdd1c2ef7ed8",
+            "7013cf6067cb57284bca029b1cd89e9727d3447eac29314a7ca1ff58d3c20bd2",
+        );
+    }
+}
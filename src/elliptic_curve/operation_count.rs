@@ -0,0 +1,117 @@
+#[cfg(any(test, feature = "operation-count"))]
+use std::cell::Cell;
+
+/// Cantidad de duplicaciones y sumas de puntos hechas por un calculo. Se cuentan las operaciones de
+/// grupo que usan las formulas (afines o jacobianas); las que involucran al punto del infinito son
+/// gratis y no se cuentan.
+///
+/// El contador cuesta un acceso a una variable del hilo por operacion, asi que solo existe en los
+/// tests o con el feature `operation-count`; si no, `record_*` no hacen nada.
+#[cfg(any(test, feature = "operation-count"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationCount {
+    pub doublings: usize,
+    pub additions: usize,
+}
+
+#[cfg(any(test, feature = "operation-count"))]
+thread_local! {
+    static COUNT: Cell<OperationCount> = Cell::new(OperationCount::default());
+}
+
+/// Ejecuta `f` y devuelve su resultado junto con las duplicaciones y sumas que hizo en este hilo.
+/// Sirve para comparar los metodos de multiplicacion escalar.
+///
+/// ```
+/// use tp1::elliptic_curve::named_curves::TP1_P1021;
+/// use tp1::elliptic_curve::operation_count::count_operations;
+///
+/// let g = TP1_P1021.domain_parameters::<i32>().unwrap().g;
+/// let (_, count) = count_operations(|| g.mul_scalar(0b1111111111));
+/// assert_eq!((count.doublings, count.additions), (9, 9));
+/// ```
+#[cfg(any(test, feature = "operation-count"))]
+pub fn count_operations<R>(f: impl FnOnce() -> R) -> (R, OperationCount) {
+    let before = COUNT.with(|count| count.get());
+    let result = f();
+    let after = COUNT.with(|count| count.get());
+    (
+        result,
+        OperationCount {
+            doublings: after.doublings - before.doublings,
+            additions: after.additions - before.additions,
+        },
+    )
+}
+
+pub(crate) fn record_doubling() {
+    #[cfg(any(test, feature = "operation-count"))]
+    COUNT.with(|count| {
+        let mut value = count.get();
+        value.doublings += 1;
+        count.set(value);
+    });
+}
+
+pub(crate) fn record_addition() {
+    #[cfg(any(test, feature = "operation-count"))]
+    COUNT.with(|count| {
+        let mut value = count.get();
+        value.additions += 1;
+        count.set(value);
+    });
+}
+
+#[cfg(test)]
+mod test_operation_count {
+    use crate::elliptic_curve::named_curves::TP1_P1021;
+
+    use super::*;
+
+    #[test]
+    fn test_01_double_and_add_counts() {
+        let g = TP1_P1021.domain_parameters::<i32>().unwrap().g;
+        // 2^9: solo duplicaciones
+        let (_, count) = count_operations(|| g.mul_scalar(512));
+        assert_eq!(
+            count,
+            OperationCount {
+                doublings: 9,
+                additions: 0
+            }
+        );
+
+        // 655 = 0b1010001111: 9 duplicaciones y una suma por cada 1 despues del primero
+        let (_, count) = count_operations(|| g.mul_scalar(655));
+        assert_eq!(
+            count,
+            OperationCount {
+                doublings: 9,
+                additions: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_02_nested_counts() {
+        let g = TP1_P1021.domain_parameters::<i32>().unwrap().g;
+        let ((_, inner), outer) = count_operations(|| {
+            let _ = (g + g).unwrap();
+            count_operations(|| g.mul_scalar(3))
+        });
+        assert_eq!(
+            inner,
+            OperationCount {
+                doublings: 1,
+                additions: 1
+            }
+        );
+        assert_eq!(
+            outer,
+            OperationCount {
+                doublings: 2,
+                additions: 1
+            }
+        );
+    }
+}
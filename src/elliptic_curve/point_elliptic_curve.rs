@@ -1,11 +1,27 @@
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 use num::{traits::Pow, One, Zero};
 
-#[derive(Debug, Clone, Copy, Hash)]
+use crate::finite_body::finite_body::FiniteBody;
+
+use super::{
+    curve::is_singular,
+    error::EllipticCurveError,
+    operation_count::{record_addition, record_doubling},
+};
+
+/// Coordenadas de un punto: el punto del infinito (el neutro del grupo) o un punto afin (x, y).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Coordinates<T> {
+    Identity,
+    Affine { x: T, y: T },
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct PointEllipticCurve<T> {
-    pub x: Option<T>,
-    pub y: Option<T>,
+    pub coordinates: Coordinates<T>,
     pub a: T,
     pub b: T,
 }
@@ -24,26 +40,66 @@ where
         + One,
 {
     pub fn new(x: T, y: T, a: T, b: T) -> Option<PointEllipticCurve<T>> {
+        Self::try_new(x, y, a, b).ok()
+    }
+
+    /// Igual que `new` pero indicando por que no se pudo crear el punto.
+    pub fn try_new(x: T, y: T, a: T, b: T) -> Result<PointEllipticCurve<T>, EllipticCurveError> {
+        if is_singular(a, b) {
+            return Err(EllipticCurveError::SingularCurve);
+        }
+        Self::on_curve(x, y, a, b).ok_or(EllipticCurveError::PointNotOnCurve)
+    }
+
+    /// Como `new` pero sin volver a mirar si la curva es singular, para `Curve` y la ley de grupo,
+    /// que parten de una curva ya verificada. Solo se comprueba la ecuacion: con coordenadas
+    /// enteras la suma puede caer fuera de la curva.
+    pub(crate) fn on_curve(x: T, y: T, a: T, b: T) -> Option<PointEllipticCurve<T>> {
         if y.pow(2) != (x.pow(3) + a * x + b) {
             return None;
         }
+        Some(Self::new_unchecked(x, y, a, b))
+    }
 
-        Some(PointEllipticCurve {
-            x: Some(x),
-            y: Some(y),
+    pub fn new_inf(a: T, b: T) -> PointEllipticCurve<T> {
+        PointEllipticCurve {
+            coordinates: Coordinates::Identity,
             a,
             b,
-        })
+        }
     }
+}
 
-    pub fn new_inf(a: T, b: T) -> PointEllipticCurve<T> {
+impl<T: Copy> PointEllipticCurve<T> {
+    /// Punto (x, y) sin ninguna verificacion, para resultados que ya se sabe que estan en la curva.
+    pub(crate) fn new_unchecked(x: T, y: T, a: T, b: T) -> PointEllipticCurve<T> {
         PointEllipticCurve {
-            x: None,
-            y: None,
+            coordinates: Coordinates::Affine { x, y },
             a,
             b,
         }
     }
+
+    /// true si es el punto del infinito.
+    pub fn is_identity(&self) -> bool {
+        matches!(self.coordinates, Coordinates::Identity)
+    }
+
+    /// (x, y), o None para el punto del infinito.
+    pub fn coordinates(&self) -> Option<(T, T)> {
+        match self.coordinates {
+            Coordinates::Identity => None,
+            Coordinates::Affine { x, y } => Some((x, y)),
+        }
+    }
+
+    pub fn x(&self) -> Option<T> {
+        self.coordinates().map(|(x, _)| x)
+    }
+
+    pub fn y(&self) -> Option<T> {
+        self.coordinates().map(|(_, y)| y)
+    }
 }
 
 impl<T> Add for PointEllipticCurve<T>
@@ -66,38 +122,42 @@ where
             return None;
         }
 
-        if self.x.is_none() && self.y.is_none() {
-            return Some(other); // self is point infinity or the additive identity.
-        }
+        let (x1, y1) = match self.coordinates {
+            Coordinates::Identity => return Some(other), // self is point infinity or the additive identity.
+            Coordinates::Affine { x, y } => (x, y),
+        };
 
-        if other.x.is_none() && other.y.is_none() {
-            return Some(self); // other is point infinity or the additive identity.
-        }
+        let (x2, y2) = match other.coordinates {
+            Coordinates::Identity => return Some(self), // other is point infinity or the additive identity.
+            Coordinates::Affine { x, y } => (x, y),
+        };
 
         // Handle the case where the two points are additive inverses (that is, they have the same
         // x but a different y, causing a vertical line). This should return the point at infinity
-        if self.x == other.x && self.y != other.y {
+        if x1 == x2 && y1 != y2 {
             return Some(PointEllipticCurve::new_inf(self.a, self.b));
         }
 
-        if self == other && self.y.unwrap() == T::zero() {
+        if self == other && y1 == T::zero() {
             return Some(PointEllipticCurve::new_inf(self.a, self.b));
         }
 
-        if self.x == other.x && self.y == other.y {
+        if x1 == x2 && y1 == y2 {
             // The two points are the same, so we need to double
-            let s = (((self.x.unwrap().pow(2)) * 3_isize) + self.a) / (self.y.unwrap() * 2_isize);
-            let x = s.pow(2) - (self.x.unwrap() * 2_isize);
-            let y = (s * (self.x.unwrap() - x)) - self.y.unwrap();
+            record_doubling();
+            let s = (((x1.pow(2)) * 3_isize) + self.a) / (y1 * 2_isize);
+            let x = s.pow(2) - (x1 * 2_isize);
+            let y = (s * (x1 - x)) - y1;
 
-            return PointEllipticCurve::new(x, y, self.a, self.b);
+            return PointEllipticCurve::on_curve(x, y, self.a, self.b);
         }
 
-        let s = (self.y.unwrap() - other.y.unwrap()) / (self.x.unwrap() - other.x.unwrap());
-        let x = s.pow(2) - self.x.unwrap() - other.x.unwrap();
-        let y = (s * (other.x.unwrap() - x)) - other.y.unwrap();
+        record_addition();
+        let s = (y1 - y2) / (x1 - x2);
+        let x = s.pow(2) - x1 - x2;
+        let y = (s * (x2 - x)) - y2;
 
-        PointEllipticCurve::new(x, y, self.a, self.b)
+        PointEllipticCurve::on_curve(x, y, self.a, self.b)
     }
 }
 
@@ -118,6 +178,7 @@ where
 
     /// Multiplicacion de un escalar entero usize por un punto de la curva eliptica. Esta operacion era necesaria
     /// para calcular la multiplicacion por 2 o por 3 en la formulas del ADD de los puntos de la curva eliptica.
+    /// Se calcula con `mul_scalar`.
     ///
     /// Por ejemplo:
     /// (-1, -1) + (-1, -1) = 2 * (-1, -1)
@@ -131,26 +192,35 @@ where
     /// ```
     ///
     fn mul(self, other: usize) -> Self::Output {
-        let mut result = PointEllipticCurve::new_inf(self.a, self.b);
-
-        for _ in 0..other {
-            let partial = result + self;
-            if partial.is_none() {
-                panic!("The point is not on the curve");
-            }
-            result = partial.unwrap();
-        }
-
-        result
+        self.mul_scalar(other)
     }
 }
 
 impl<T> Neg for PointEllipticCurve<T>
+where
+    T: Neg<Output = T> + Copy,
+{
+    type Output = Self;
+
+    /// -(x, y) = (x, -y), que esta en la curva si (x, y) lo esta. -O = O.
+    fn neg(self) -> Self::Output {
+        match self.coordinates {
+            Coordinates::Identity => self,
+            Coordinates::Affine { x, y } => PointEllipticCurve {
+                coordinates: Coordinates::Affine { x, y: -y },
+                ..self
+            },
+        }
+    }
+}
+
+impl<T> Sub for PointEllipticCurve<T>
 where
     T: Add<T>
-        + Div<T>
+        + Div<Output = T>
         + Sub<Output = T>
         + Neg<Output = T>
+        + Mul<isize, Output = T>
         + Pow<usize, Output = T>
         + PartialOrd
         + Copy
@@ -159,11 +229,130 @@ where
 {
     type Output = Option<Self>;
 
-    fn neg(self) -> Self::Output {
-        if self.x.is_none() && self.y.is_none() {
-            return Some(self);
-        }
-        PointEllipticCurve::new(self.x.unwrap(), -self.y.unwrap(), self.a, self.b)
+    /// P - Q = P + (-Q). None si los puntos no estan en la misma curva, como la suma.
+    fn sub(self, other: Self) -> Self::Output {
+        self + (-other)
+    }
+}
+
+// Para encadenar: (P + Q) + R y (P + Q) - R, propagando el None de una suma entre curvas distintas.
+impl<T> Add<PointEllipticCurve<T>> for Option<PointEllipticCurve<T>>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Option<PointEllipticCurve<T>>;
+
+    fn add(self, other: PointEllipticCurve<T>) -> Self::Output {
+        self? + other
+    }
+}
+
+impl<T> Sub<PointEllipticCurve<T>> for Option<PointEllipticCurve<T>>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Option<PointEllipticCurve<T>>;
+
+    fn sub(self, other: PointEllipticCurve<T>) -> Self::Output {
+        self? - other
+    }
+}
+
+impl<T> AddAssign for PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// P += Q. Panics si los puntos no estan en la misma curva.
+    fn add_assign(&mut self, other: Self) {
+        *self = (*self + other).expect("Points are not on the same curve");
+    }
+}
+
+impl<T> SubAssign for PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// P -= Q. Panics si los puntos no estan en la misma curva.
+    fn sub_assign(&mut self, other: Self) {
+        *self = (*self - other).expect("Points are not on the same curve");
+    }
+}
+
+impl<T> Sum<PointEllipticCurve<T>> for Option<PointEllipticCurve<T>>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Suma de todos los puntos. None si no hay ninguno (no se sabe de que curva es el neutro) o si
+    /// no estan todos en la misma curva.
+    fn sum<I: Iterator<Item = PointEllipticCurve<T>>>(mut iter: I) -> Self {
+        let first = iter.next()?;
+        iter.try_fold(first, |acum, point| acum + point)
+    }
+}
+
+impl<X> PointEllipticCurve<FiniteBody<X>>
+where
+    X: Copy,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// 2P = P + P. Solo sobre un cuerpo: con coordenadas enteras la division trunca, 2P puede caer
+    /// fuera de la curva y `Add` devuelve None.
+    pub fn double(&self) -> Self {
+        (*self + *self).expect("over a field 2P is on the same curve")
     }
 }
 
@@ -173,12 +362,12 @@ where
 {
     // tuple (x, y, a, b)
     fn eq(&self, (x, y, a, b): &(T, T, T, T)) -> bool {
-        match (self.x, self.y) {
-            (None, None) => self.a == *a && self.b == *b,
-            (Some(self_x), Some(self_y)) => {
-                self_x == *x && self_y == *y && self.a == *a && self.b == *b
-            }
-            _ => false,
+        match self.coordinates {
+            Coordinates::Identity => self.a == *a && self.b == *b,
+            Coordinates::Affine {
+                x: self_x,
+                y: self_y,
+            } => self_x == *x && self_y == *y && self.a == *a && self.b == *b,
         }
     }
 }
@@ -194,10 +383,12 @@ where
 {
     // tuple (x, y)
     fn eq(&self, (x, y): &(T, T)) -> bool {
-        match (self.x, self.y) {
-            (None, None) => false,
-            (Some(self_x), Some(self_y)) => self_x == *x && self_y == *y,
-            _ => false,
+        match self.coordinates {
+            Coordinates::Identity => false,
+            Coordinates::Affine {
+                x: self_x,
+                y: self_y,
+            } => self_x == *x && self_y == *y,
         }
     }
 }
@@ -207,13 +398,21 @@ where
     T: PartialEq + std::ops::Add<T> + std::ops::Sub<Output = T> + std::ops::Neg<Output = T> + Copy,
 {
     fn eq(&self, other: &PointEllipticCurve<T>) -> bool {
-        self.x == other.x && self.y == other.y && self.a == other.a && self.b == other.b
+        self.coordinates == other.coordinates && self.a == other.a && self.b == other.b
+    }
+}
+
+impl<T: Hash> Hash for PointEllipticCurve<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.coordinates.hash(state);
+        self.a.hash(state);
+        self.b.hash(state);
     }
 }
 
 #[cfg(test)]
 mod test_points_eliptic_curve {
-    use crate::finite_body::finite_body::FiniteBody;
+    use crate::{elliptic_curve::curve::Curve, finite_body::finite_body::FiniteBody};
 
     use super::*;
 
@@ -248,8 +447,8 @@ mod test_points_eliptic_curve {
         assert_eq!(point1 + point2, Some(point_inf)); // P1 + P2 = I
         assert_eq!(point1 + point_inf, Some(point1)); // addition identity
         assert_eq!(point2 + point_inf, Some(point2)); // addition identity
-        assert_eq!(point2, (-point1).unwrap()); // P2 = -P1
-        assert_eq!(point1, (-point2).unwrap()); // P1 = -P2
+        assert_eq!(point2, -point1); // P2 = -P1
+        assert_eq!(point1, -point2); // P1 = -P2
     }
 
     #[test]
@@ -340,19 +539,18 @@ mod test_points_eliptic_curve {
         let y: FiniteBody<i32> = FiniteBody::new(p, 71);
         let g: PointEllipticCurve<FiniteBody<i32>> = PointEllipticCurve::new(x, y, a, b).unwrap();
 
-        let n = 21; // Finity group, finity cyclic group: {G, 2G, ..., nG}. . . n is order of the group
+        let n: usize = 21; // Finity group, finity cyclic group: {G, 2G, ..., nG}. . . n is order of the group
 
         // loop of {G, 2G, ..., (n-1)G}
         for k in 1..n {
-            let k = k as usize;
             let res = g * k;
             println!(
                 "{:?} * (x: {:?}, y:{:?}) = (x: {:?}, y:{:?})",
                 k,
-                g.x.unwrap().as_value(),
-                g.y.unwrap().as_value(),
-                res.x.unwrap().as_value(),
-                res.y.unwrap().as_value()
+                g.x().unwrap().as_value(),
+                g.y().unwrap().as_value(),
+                res.x().unwrap().as_value(),
+                res.y().unwrap().as_value()
             );
         }
 
@@ -371,19 +569,18 @@ mod test_points_eliptic_curve {
         let y: FiniteBody<i32> = FiniteBody::new(p, 86);
         let g: PointEllipticCurve<FiniteBody<i32>> = PointEllipticCurve::new(x, y, a, b).unwrap();
 
-        let n = 7; // Finity group, finity cyclic group: {G, 2G, ..., nG}. . . n is order of the group
+        let n: usize = 7; // Finity group, finity cyclic group: {G, 2G, ..., nG}. . . n is order of the group
 
         // loop of {G, 2G, ..., (n-1)G}
         for k in 1..n {
-            let k = k as usize;
             let res = g * k;
             println!(
                 "{:?} * (x: {:?}, y:{:?}) = (x: {:?}, y:{:?})",
                 k,
-                g.x.unwrap().as_value(),
-                g.y.unwrap().as_value(),
-                res.x.unwrap().as_value(),
-                res.y.unwrap().as_value()
+                g.x().unwrap().as_value(),
+                g.y().unwrap().as_value(),
+                res.x().unwrap().as_value(),
+                res.y().unwrap().as_value()
             );
         }
 
@@ -407,7 +604,7 @@ mod test_points_eliptic_curve {
         let y: FiniteBody<i32> = FiniteBody::new(p, 1011);
         let g: PointEllipticCurve<FiniteBody<i32>> = PointEllipticCurve::new(x, y, a, b).unwrap();
 
-        let k = 655;
+        let k: usize = 655;
 
         // http://www.christelbach.com/ECCalculator.aspx
         let x_g_expected = FiniteBody::new(p, 388);
@@ -419,29 +616,135 @@ mod test_points_eliptic_curve {
         println!(
             "kP = {:?} * (x: {:?}, y: {:?}) = (x: {:?}, y:{:?})",
             k,
-            g.x.unwrap().as_value(),
-            g.y.unwrap().as_value(),
-            res.x.unwrap().as_value(),
-            res.y.unwrap().as_value()
+            g.x().unwrap().as_value(),
+            g.y().unwrap().as_value(),
+            res.x().unwrap().as_value(),
+            res.y().unwrap().as_value()
         );
 
-        let mut points = vec![];
-
-        // Brute-force search
-        for x in 0..p {
-            let x_val = FiniteBody::new(p, x);
-            let y_squared = x_val.pow(3) + a * x_val + b;
-            for y in 0..p {
-                let y_val = FiniteBody::new(p, y);
-                if y_squared == y_val.pow(2) {
-                    let point = PointEllipticCurve::new(x_val, y_val, a, b).unwrap();
-                    points.push(point);
-                }
-            }
-        }
+        // #E = p + 1 + sum χ(x^3 + ax + b)
+        let curve = Curve::new(a, b).unwrap();
+        let quantity = curve.count_points();
+        assert_eq!(quantity, 1039);
+        assert_eq!(curve.points().count(), quantity);
         println!(
             "Cantidad de puntos de la curva: {:?} + 1 punto del infinito",
-            points.len()
+            quantity - 1
+        );
+    }
+
+    #[test]
+    fn test_10_creation_on_singular_curve() {
+        let p = 43;
+        let zero = FiniteBody::new(p, 0);
+
+        // y^2 = x^3 tiene una cuspide en (0, 0), pero (1, 1) satisface la ecuacion
+        let one = FiniteBody::new(p, 1);
+        assert!(PointEllipticCurve::new(one, one, zero, zero).is_none());
+        assert_eq!(
+            PointEllipticCurve::try_new(one, one, zero, zero),
+            Err(EllipticCurveError::SingularCurve)
+        );
+        assert_eq!(
+            PointEllipticCurve::try_new(one, one, zero, FiniteBody::new(p, 6)),
+            Err(EllipticCurveError::PointNotOnCurve)
         );
     }
+
+    #[test]
+    fn test_11_scalar_mul_with_integer_coordinates() {
+        // con enteros la division trunca: k * P tiene que ser P + P + ... + P
+        let p = PointEllipticCurve::new(-1, -1, 5, 7).unwrap();
+        let mut sum = PointEllipticCurve::new_inf(5, 7);
+        for k in 0..8_usize {
+            assert_eq!(p * k, sum, "k = {}", k);
+            sum = (sum + p).unwrap();
+        }
+        assert_eq!(p * 2_usize, (18, 77));
+    }
+
+    #[test]
+    fn test_12_accessors() {
+        let point = PointEllipticCurve::new(-1, -1, 5, 7).unwrap();
+        assert!(!point.is_identity());
+        assert_eq!(point.coordinates(), Some((-1, -1)));
+        assert_eq!(point.x(), Some(-1));
+        assert_eq!(point.y(), Some(-1));
+        assert_eq!(point.coordinates, Coordinates::Affine { x: -1, y: -1 });
+
+        let identity = PointEllipticCurve::new_inf(5, 7);
+        assert!(identity.is_identity());
+        assert_eq!(identity.coordinates(), None);
+        assert_eq!(identity.x(), None);
+        assert_eq!(identity, (0, 0, 5, 7)); // el infinito solo compara la curva
+        assert_ne!(identity, point);
+    }
+
+    #[test]
+    fn test_13_group_law_conveniences() {
+        let p = 223;
+        let (a, b) = (FiniteBody::new(p, 0), FiniteBody::new(p, 7));
+        let g =
+            PointEllipticCurve::new(FiniteBody::new(p, 47), FiniteBody::new(p, 71), a, b).unwrap();
+        let identity = PointEllipticCurve::new_inf(a, b);
+
+        assert_eq!(-identity, identity);
+        assert_eq!((g + -g).unwrap(), identity);
+        assert_eq!(g.double(), g * 2);
+        assert_eq!((g * 5_usize - g * 3_usize).unwrap(), g * 2);
+        assert_eq!((g - g).unwrap(), identity);
+        assert_eq!((g + g + g - g * 3).unwrap(), identity);
+        assert_eq!(g * -4_i64, -(g * 4_i64));
+        assert_eq!(g * -4_isize + g * 4_isize, Some(identity));
+
+        let mut acum = identity;
+        acum += g;
+        acum += g;
+        assert_eq!(acum, g * 2);
+        acum -= g * 3_usize;
+        assert_eq!(acum, -g);
+
+        let sum: Option<_> = (1..=6_usize).map(|k| g * k).sum();
+        assert_eq!(sum.unwrap(), g * 21_usize);
+        let empty: Option<PointEllipticCurve<FiniteBody<i32>>> = Vec::new().into_iter().sum();
+        assert!(empty.is_none());
+    }
+
+    #[test]
+    fn test_14_operations_between_curves() {
+        let p = 223;
+        let g = PointEllipticCurve::new(
+            FiniteBody::new(p, 47),
+            FiniteBody::new(p, 71),
+            FiniteBody::new(p, 0),
+            FiniteBody::new(p, 7),
+        )
+        .unwrap();
+        let other = PointEllipticCurve::new(
+            FiniteBody::new(p, 0),
+            FiniteBody::new(p, 1),
+            FiniteBody::new(p, 1),
+            FiniteBody::new(p, 1),
+        )
+        .unwrap();
+
+        assert!((g - other).is_none());
+        assert!((g + g - other).is_none());
+        let sum: Option<_> = [g, other].into_iter().sum();
+        assert!(sum.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Points are not on the same curve")]
+    fn test_15_add_assign_between_curves_panics() {
+        let p = 223;
+        let mut g = PointEllipticCurve::new(
+            FiniteBody::new(p, 47),
+            FiniteBody::new(p, 71),
+            FiniteBody::new(p, 0),
+            FiniteBody::new(p, 7),
+        )
+        .unwrap();
+        g += PointEllipticCurve::new_inf(FiniteBody::new(p, 1), FiniteBody::new(p, 1));
+    }
 }
@@ -0,0 +1,154 @@
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{big_integer::BigInteger, factorization::factor, finite_body::finite_body::FiniteBody};
+
+use super::{curve::Curve, point_elliptic_curve::PointEllipticCurve};
+
+impl<T> PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Orden de P dado un multiplo suyo, normalmente #E: se factoriza y se saca cada primo q mientras
+    /// (n / q) * P = O. Son a lo sumo tantas multiplicaciones como factores primos tiene n.
+    ///
+    /// Panics si `group_order` no anula a P.
+    pub fn order_with_group_order<N: Into<BigInteger>>(&self, group_order: N) -> BigInteger {
+        let group_order = group_order.into();
+        assert!(
+            self.mul_scalar(group_order).is_identity(),
+            "the group order does not annihilate the point"
+        );
+
+        let mut order = group_order;
+        for (prime, exponent) in factor(group_order) {
+            for _ in 0..exponent {
+                let cofactor = order / prime;
+                if !self.mul_scalar(cofactor).is_identity() {
+                    break;
+                }
+                order = cofactor;
+            }
+        }
+        order
+    }
+}
+
+impl<X> PointEllipticCurve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Hash
+        + Into<BigInteger>
+        + TryFrom<BigInteger>,
+{
+    /// Orden de P calculando antes #E con `Curve::group_order`. Si ya se conoce #E conviene usar
+    /// `order_with_group_order`.
+    pub fn order(&self) -> BigInteger {
+        let curve = Curve {
+            a: self.a,
+            b: self.b,
+        };
+        self.order_with_group_order(curve.group_order())
+    }
+}
+
+#[cfg(test)]
+mod test_point_order {
+    use crate::elliptic_curve::named_curves::{SECP256K1, SECP256R1, TP1_P1021};
+
+    use super::*;
+
+    // orden por fuerza bruta
+    fn naive_order(point: &PointEllipticCurve<FiniteBody<i64>>) -> BigInteger {
+        let mut multiple = *point;
+        let mut k = 1_u64;
+        while !multiple.is_identity() {
+            multiple = (multiple + *point).unwrap();
+            k += 1;
+        }
+        BigInteger::from(k)
+    }
+
+    #[test]
+    fn test_01_ejercicio3_orders() {
+        // y^2 = x^3 + 6 sobre F_43: G1 genera el subgrupo de orden 13 y G2 toda la curva
+        let p = 43_i64;
+        let (a, b) = (FiniteBody::new(p, 0), FiniteBody::new(p, 6));
+        let g1 = PointEllipticCurve::new(FiniteBody::new(p, 13), FiniteBody::new(p, 15), a, b);
+        let g2 = PointEllipticCurve::new(FiniteBody::new(p, 9), FiniteBody::new(p, 2), a, b);
+        assert_eq!(
+            g1.unwrap().order_with_group_order(39_u64),
+            BigInteger::from(13)
+        );
+        assert_eq!(
+            g2.unwrap().order_with_group_order(39_u64),
+            BigInteger::from(39)
+        );
+        assert_eq!(g1.unwrap().order(), BigInteger::from(13));
+        assert_eq!(
+            PointEllipticCurve::new_inf(a, b).order_with_group_order(39_u64),
+            BigInteger::one()
+        );
+    }
+
+    #[test]
+    fn test_02_matches_naive_order() {
+        let p = 1021_i64;
+        let curve = Curve::new(FiniteBody::new(p, -3), FiniteBody::new(p, -3)).unwrap();
+        let group_order = curve.group_order();
+        for x in 0..60 {
+            let x = FiniteBody::new(p, x);
+            let Some(y) = (x * x * x + curve.a * x + curve.b).sqrt() else {
+                continue;
+            };
+            let point = curve.point(x, y).unwrap();
+            assert_eq!(
+                point.order_with_group_order(group_order),
+                naive_order(&point),
+                "x = {}",
+                x.value
+            );
+        }
+    }
+
+    #[test]
+    fn test_03_named_curves() {
+        for named_curve in [SECP256K1, SECP256R1, TP1_P1021] {
+            let params = named_curve.domain_parameters::<BigInteger>().unwrap();
+            assert_eq!(
+                params.g.order_with_group_order(params.n * params.h),
+                params.n
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the group order does not annihilate the point")]
+    fn test_04_wrong_group_order() {
+        let p = 43_i64;
+        let (a, b) = (FiniteBody::new(p, 0), FiniteBody::new(p, 6));
+        let g1 = PointEllipticCurve::new(FiniteBody::new(p, 13), FiniteBody::new(p, 15), a, b);
+        g1.unwrap().order_with_group_order(40_u64);
+    }
+}
@@ -0,0 +1,248 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::thread;
+
+use num::{traits::Pow, One, Zero};
+
+use crate::finite_body::finite_body::FiniteBody;
+
+use super::{
+    curve::Curve,
+    point_elliptic_curve::{Coordinates, PointEllipticCurve},
+};
+
+/// Iterador sobre los puntos de E(F_p), devuelto por `Curve::points`.
+#[derive(Debug, Clone)]
+pub struct Points<X> {
+    curve: Curve<FiniteBody<X>>,
+    identity_pending: bool,
+    x: X,
+    end: X,
+    // (x, -y) despues de devolver (x, y)
+    pending: Option<PointEllipticCurve<FiniteBody<X>>>,
+}
+
+impl<X> Iterator for Points<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Item = PointEllipticCurve<FiniteBody<X>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.identity_pending {
+            self.identity_pending = false;
+            return Some(self.curve.identity());
+        }
+        if let Some(point) = self.pending.take() {
+            return Some(point);
+        }
+
+        let (a, b) = (self.curve.a, self.curve.b);
+        let p = a.p;
+        while self.x < self.end {
+            let x = FiniteBody::new(p, self.x);
+            self.x = self.x + X::one();
+            let Some(y) = (x * x * x + a * x + b).sqrt() else {
+                continue;
+            };
+            let point = |y| PointEllipticCurve {
+                coordinates: Coordinates::Affine { x, y },
+                a,
+                b,
+            };
+            if y.value != X::zero() {
+                self.pending = Some(point(-y));
+            }
+            return Some(point(y));
+        }
+        None
+    }
+}
+
+/// Iterador sobre el subgrupo generado por G, devuelto por `Curve::subgroup`.
+#[derive(Debug, Clone)]
+pub struct Subgroup<T> {
+    generator: PointEllipticCurve<T>,
+    current: Option<PointEllipticCurve<T>>,
+}
+
+impl<T> Iterator for Subgroup<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Item = PointEllipticCurve<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        self.current = match current.is_identity() {
+            true => None,
+            false => Some((current + self.generator).unwrap()),
+        };
+        Some(current)
+    }
+}
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// Los puntos de E(F_p) sin repetir: primero el punto del infinito y despues, para x = 0, 1, ...,
+    /// p - 1, los puntos (x, y) y (x, -y) con y una raiz de x^3 + ax + b (uno solo si y = 0). Se
+    /// calculan a medida que se piden, con una raiz cuadrada por cada x en lugar de probar los p
+    /// valores de y.
+    pub fn points(&self) -> Points<X> {
+        self.points_in_range(X::zero(), self.a.p, true)
+    }
+
+    /// Los mismos puntos que `points`, en el mismo orden, repartiendo los valores de x en `threads`
+    /// tramos consecutivos, uno por hilo. Con `threads` = 0 se usan los hilos que tenga disponibles
+    /// la maquina. A diferencia de `points` los guarda todos en memoria.
+    pub fn points_parallel(&self, threads: usize) -> Vec<PointEllipticCurve<FiniteBody<X>>>
+    where
+        X: Send + Sync,
+    {
+        let threads = match threads {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            threads => threads,
+        };
+        let p = self.a.p;
+        let threads_value = (0..threads).fold(X::zero(), |acc, _| acc + X::one());
+        let chunk = p / threads_value + X::one();
+
+        let chunks: Vec<Vec<_>> = thread::scope(|scope| {
+            let mut start = X::zero();
+            let mut handles = vec![];
+            for _ in 0..threads {
+                let end = if p - start > chunk { start + chunk } else { p };
+                handles
+                    .push(scope.spawn(move || self.points_in_range(start, end, false).collect()));
+                start = end;
+            }
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("point enumeration thread panicked"))
+                .collect()
+        });
+
+        let mut points = vec![self.identity()];
+        points.extend(chunks.into_iter().flatten());
+        points
+    }
+
+    /// Los puntos del subgrupo generado por G: G, 2G, 3G, ... hasta volver a O, que es el ultimo.
+    /// Devuelve tantos puntos como el orden de G. Panics si G no esta en la curva.
+    pub fn subgroup(&self, generator: PointEllipticCurve<FiniteBody<X>>) -> Subgroup<FiniteBody<X>>
+    where
+        FiniteBody<X>: Mul<isize, Output = FiniteBody<X>>,
+    {
+        assert!(
+            self.contains(&generator),
+            "the generator is not on the curve"
+        );
+        Subgroup {
+            generator,
+            current: Some(generator),
+        }
+    }
+
+    // Puntos con start <= x < end, y el del infinito si `identity` es true
+    fn points_in_range(&self, start: X, end: X, identity: bool) -> Points<X> {
+        Points {
+            curve: *self,
+            identity_pending: identity,
+            x: start,
+            end,
+            pending: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_points {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_01_each_point_once() {
+        for (p, a, b) in [(43_i64, 0, 6), (1021, -3, -3), (61, 0, 1), (1019, -1, 0)] {
+            let curve = Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap();
+            let points: Vec<_> = curve.points().collect();
+            assert_eq!(points.len(), curve.count_points(), "p = {}", p);
+            assert!(points[0].is_identity());
+            assert!(points.iter().all(|point| curve.contains(point)));
+            let distinct: HashSet<_> = points.iter().collect();
+            assert_eq!(distinct.len(), points.len());
+        }
+    }
+
+    #[test]
+    fn test_02_lazy() {
+        // 2^40 + 15: recorrer todos los x no terminaria, pero los primeros puntos salen enseguida
+        let p: i128 = (1 << 40) + 15;
+        let curve = Curve::new(FiniteBody::new(p, 3), FiniteBody::new(p, 11)).unwrap();
+        let points: Vec<_> = curve.points().skip(1).take(10).collect();
+        assert_eq!(points.len(), 10);
+        assert!(points.iter().all(|point| curve.contains(point)));
+    }
+
+    #[test]
+    fn test_03_parallel_matches_sequential() {
+        let curve = Curve::new(FiniteBody::new(1021_i64, -3), FiniteBody::new(1021, -3)).unwrap();
+        let points: Vec<_> = curve.points().collect();
+        for threads in [0, 1, 2, 3, 8] {
+            assert_eq!(curve.points_parallel(threads), points);
+        }
+    }
+
+    #[test]
+    fn test_04_subgroup() {
+        // en y^2 = x^3 + 6 sobre F_43, G1 = (13, 15) tiene orden 13 y G2 = (9, 2) genera toda la curva
+        let p = 43_i64;
+        let curve = Curve::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).unwrap();
+        let g1 = curve
+            .point(FiniteBody::new(p, 13), FiniteBody::new(p, 15))
+            .unwrap();
+        let g2 = curve
+            .point(FiniteBody::new(p, 9), FiniteBody::new(p, 2))
+            .unwrap();
+
+        let subgroup: Vec<_> = curve.subgroup(g1).collect();
+        assert_eq!(subgroup.len(), 13);
+        assert_eq!(subgroup[0], g1);
+        assert_eq!(subgroup[4], g1 * 5_usize);
+        assert!(subgroup[12].is_identity());
+
+        let all: HashSet<_> = curve.subgroup(g2).collect();
+        assert_eq!(all, curve.points().collect());
+        assert_eq!(curve.subgroup(curve.identity()).count(), 1);
+    }
+}
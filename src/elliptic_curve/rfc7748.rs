@@ -0,0 +1,262 @@
+use num::One;
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::montgomery::MontgomeryCurve;
+
+/// Largo en bytes de las claves y coordenadas u de X25519.
+pub const X25519_SIZE: usize = 32;
+/// Largo en bytes de las claves y coordenadas u de X448.
+pub const X448_SIZE: usize = 56;
+/// u = 9, el punto base de Curve25519, codificado.
+pub const X25519_BASE_POINT: [u8; X25519_SIZE] = base_point(9);
+/// u = 5, el punto base de Curve448, codificado.
+pub const X448_BASE_POINT: [u8; X448_SIZE] = base_point(5);
+
+/// Curve25519: y^2 = x^3 + 486662x^2 + x sobre F_p con p = 2^255 - 19.
+pub fn curve25519() -> MontgomeryCurve<FiniteBody<BigInteger>> {
+    let p = (BigInteger::one() << 255) - BigInteger::from(19);
+    montgomery_curve(p, 486662)
+}
+
+/// Curve448: y^2 = x^3 + 156326x^2 + x sobre F_p con p = 2^448 - 2^224 - 1.
+pub fn curve448() -> MontgomeryCurve<FiniteBody<BigInteger>> {
+    let p = (BigInteger::one() << 448) - (BigInteger::one() << 224) - BigInteger::one();
+    montgomery_curve(p, 156326)
+}
+
+/// X25519(k, u) (RFC 7748, 5): la coordenada u de k * P, con u = u(P), sobre Curve25519. El escalar
+/// se ajusta con `decode_scalar_25519` y el bit mas alto de u se ignora. Con u = 9 da la clave
+/// publica de k, y con la clave publica del otro, el secreto compartido.
+pub fn x25519(k: [u8; X25519_SIZE], u: [u8; X25519_SIZE]) -> [u8; X25519_SIZE] {
+    let mut u = u;
+    u[X25519_SIZE - 1] &= 0x7f;
+    scalar_multiple(&curve25519(), decode_scalar_25519(k), u, 255)
+}
+
+/// X448(k, u) (RFC 7748, 5): como `x25519` sobre Curve448, con el escalar ajustado por
+/// `decode_scalar_448`.
+pub fn x448(k: [u8; X448_SIZE], u: [u8; X448_SIZE]) -> [u8; X448_SIZE] {
+    scalar_multiple(&curve448(), decode_scalar_448(k), u, 448)
+}
+
+/// Escalar de X25519 a partir de 32 bytes little endian: se borran los 3 bits mas bajos (el
+/// resultado es multiplo del cofactor 8) y el bit 255, y se prende el bit 254, asi la escalera
+/// siempre hace la misma cantidad de pasos.
+pub fn decode_scalar_25519(k: [u8; X25519_SIZE]) -> BigInteger {
+    let mut k = k;
+    k[0] &= 0xf8;
+    k[X25519_SIZE - 1] &= 0x7f;
+    k[X25519_SIZE - 1] |= 0x40;
+    from_bytes_le(&k)
+}
+
+/// Escalar de X448 a partir de 56 bytes little endian: se borran los 2 bits mas bajos (cofactor 4)
+/// y se prende el bit 447.
+pub fn decode_scalar_448(k: [u8; X448_SIZE]) -> BigInteger {
+    let mut k = k;
+    k[0] &= 0xfc;
+    k[X448_SIZE - 1] |= 0x80;
+    from_bytes_le(&k)
+}
+
+// u(k * P) codificado; los valores de u mayores o iguales a p se reducen
+fn scalar_multiple<const N: usize>(
+    curve: &MontgomeryCurve<FiniteBody<BigInteger>>,
+    k: BigInteger,
+    u: [u8; N],
+    bits: usize,
+) -> [u8; N] {
+    let u = FiniteBody::new(curve.a.p, from_bytes_le(&u));
+    let mut bytes = curve.ladder(k, u, bits).value.to_bytes_be_padded(N);
+    bytes.reverse();
+    bytes.try_into().expect("the u-coordinate has N bytes")
+}
+
+fn montgomery_curve(p: BigInteger, a: i64) -> MontgomeryCurve<FiniteBody<BigInteger>> {
+    let (a, b) = (
+        FiniteBody::new(p, a.into()),
+        FiniteBody::new(p, BigInteger::one()),
+    );
+    MontgomeryCurve::new(a, b).expect("the curve is not singular")
+}
+
+fn from_bytes_le(bytes: &[u8]) -> BigInteger {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    BigInteger::from_bytes_be(&bytes)
+}
+
+const fn base_point<const N: usize>(u: u8) -> [u8; N] {
+    let mut bytes = [0; N];
+    bytes[0] = u;
+    bytes
+}
+
+#[cfg(test)]
+mod test_rfc7748 {
+    use super::*;
+
+    fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        bytes.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_01_x25519_vectors() {
+        // RFC 7748, 5.2
+        assert_eq!(
+            x25519(
+                bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                bytes("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c")
+            ),
+            bytes::<32>("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+        assert_eq!(
+            x25519(
+                bytes("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"),
+                bytes("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493")
+            ),
+            bytes::<32>("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+        );
+        // primera iteracion de la prueba con k = u = 9
+        assert_eq!(
+            x25519(X25519_BASE_POINT, X25519_BASE_POINT),
+            bytes::<32>("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+    }
+
+    #[test]
+    fn test_02_x448_vectors() {
+        // RFC 7748, 5.2
+        assert_eq!(
+            x448(
+                bytes(
+                    "3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78f\
+                     bff44943eba368f54b29259a4f1c600ad3"
+                ),
+                bytes(
+                    "06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da\
+                     2583fa5429db94ada18aa7a7fb4ef8a086"
+                )
+            ),
+            bytes::<56>(
+                "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0\
+                 779d98223961111e21766282f73dd96b6f"
+            )
+        );
+        assert_eq!(
+            x448(
+                bytes(
+                    "203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46\
+                     d3315c44e0a5b4371282dd2c8d5be3095f"
+                ),
+                bytes(
+                    "0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570be\
+                     e52fe205e28a78b91cdfbde71ce8d157db"
+                )
+            ),
+            bytes::<56>(
+                "884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d6\
+                 2077e63633c575c1c954514e99da7c179d"
+            )
+        );
+    }
+
+    #[test]
+    fn test_03_x25519_diffie_hellman() {
+        // RFC 7748, 6.1
+        let alice: [u8; 32] =
+            bytes("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob: [u8; 32] =
+            bytes("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let public_alice = x25519(alice, X25519_BASE_POINT);
+        let public_bob = x25519(bob, X25519_BASE_POINT);
+        assert_eq!(
+            public_alice,
+            bytes::<32>("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            public_bob,
+            bytes::<32>("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+
+        let shared: [u8; 32] =
+            bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(x25519(alice, public_bob), shared);
+        assert_eq!(x25519(bob, public_alice), shared);
+    }
+
+    #[test]
+    fn test_04_x448_diffie_hellman() {
+        // RFC 7748, 6.2
+        let alice: [u8; 56] = bytes(
+            "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391\
+             006382a6f127ab1d9ac2d8c0a598726b",
+        );
+        let bob: [u8; 56] = bytes(
+            "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21\
+             374c9c921b09d1b0366f10b65173992d",
+        );
+        let public_alice = x448(alice, X448_BASE_POINT);
+        let public_bob = x448(bob, X448_BASE_POINT);
+        assert_eq!(
+            public_alice,
+            bytes::<56>(
+                "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d4\
+                 0c5b12da88120d53177f80e532c41fa0"
+            )
+        );
+        assert_eq!(
+            public_bob,
+            bytes::<56>(
+                "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13c\
+                 a706dcb57aec3dae07bdc1c67bf33609"
+            )
+        );
+
+        let shared: [u8; 56] = bytes(
+            "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936\
+             521c24403085d59a449a5037514a879d",
+        );
+        assert_eq!(x448(alice, public_bob), shared);
+        assert_eq!(x448(bob, public_alice), shared);
+    }
+
+    #[test]
+    fn test_05_clamping() {
+        // los bits que borra o prende decode_scalar no cambian el resultado
+        let k: [u8; 32] = bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+        let mut unclamped = k;
+        unclamped[0] |= 0x07;
+        unclamped[31] ^= 0x80;
+        assert_eq!(decode_scalar_25519(unclamped), decode_scalar_25519(k));
+        assert_eq!(decode_scalar_25519(k).bits(), 255);
+        assert!(!decode_scalar_25519(k).bit(0));
+
+        let k448 = [0xff; 56];
+        let decoded = decode_scalar_448(k448);
+        assert_eq!(decoded.bits(), 448);
+        assert!(!decoded.bit(0) && !decoded.bit(1));
+        assert_eq!(decode_scalar_448([0; 56]), BigInteger::one() << 447);
+
+        // el bit mas alto de u se ignora en X25519
+        let mut u = X25519_BASE_POINT;
+        u[31] |= 0x80;
+        assert_eq!(x25519(k, u), x25519(k, X25519_BASE_POINT));
+    }
+
+    #[test]
+    fn test_06_small_order_points_give_zero() {
+        // u = 0 es el punto (0, 0) de orden 2 y el escalar es multiplo del cofactor
+        let zero = [0; 32];
+        assert_eq!(x25519(X25519_BASE_POINT, zero), zero);
+        // u = 1 tiene orden 4 en Curve25519
+        let mut one = [0; 32];
+        one[0] = 1;
+        assert_eq!(x25519(X25519_BASE_POINT, one), zero);
+    }
+}
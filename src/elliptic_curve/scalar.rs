@@ -0,0 +1,311 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{traits::Pow, One, Zero};
+use rand::Rng;
+
+use crate::big_integer::BigInteger;
+
+use super::point_elliptic_curve::PointEllipticCurve;
+
+/// Escalar de Z/nZ, con n el orden del grupo (o del generador) de una curva. Las claves privadas,
+/// los nonces y las cuentas de las firmas viven aca y no en el cuerpo F_p de las coordenadas.
+///
+/// El valor siempre esta reducido a [0, n - 1]. Operar escalares de ordenes distintos es un error
+/// de programacion y hace panic.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Scalar {
+    value: BigInteger,
+    order: BigInteger,
+}
+
+impl Scalar {
+    /// value mod order. Panics si order < 2.
+    pub fn new<V: Into<BigInteger>, N: Into<BigInteger>>(value: V, order: N) -> Self {
+        let order = order.into();
+        assert!(order > BigInteger::one(), "the order must be at least 2");
+        Scalar {
+            value: ((value.into() % order) + order) % order,
+            order,
+        }
+    }
+
+    /// Escalar uniforme en [0, n - 1], por rechazo: se sortean bits(n) bits hasta obtener un valor
+    /// menor que n (en promedio menos de dos intentos).
+    pub fn random<N: Into<BigInteger>, R: Rng + ?Sized>(order: N, rng: &mut R) -> Self {
+        let order = order.into();
+        assert!(order > BigInteger::one(), "the order must be at least 2");
+        let bits = order.bits();
+        let mut bytes = vec![0u8; bits.div_ceil(8)];
+        loop {
+            rng.fill(&mut bytes[..]);
+            // se descartan los bits que sobran del byte mas significativo
+            if bits % 8 != 0 {
+                bytes[0] &= (1 << (bits % 8)) - 1;
+            }
+            let value = BigInteger::from_bytes_be(&bytes);
+            if value < order {
+                return Scalar { value, order };
+            }
+        }
+    }
+
+    /// Escalar uniforme en [1, n - 1], por ejemplo para una clave privada o un nonce.
+    pub fn random_nonzero<N: Into<BigInteger>, R: Rng + ?Sized>(order: N, rng: &mut R) -> Self {
+        let order = order.into();
+        loop {
+            let scalar = Self::random(order, rng);
+            if !scalar.is_zero() {
+                return scalar;
+            }
+        }
+    }
+
+    pub fn value(&self) -> BigInteger {
+        self.value
+    }
+
+    pub fn order(&self) -> BigInteger {
+        self.order
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// Inverso multiplicativo con el algoritmo de Euclides extendido. None si gcd(value, n) != 1,
+    /// lo que solo pasa con el cero cuando n es primo.
+    pub fn inverse(&self) -> Option<Self> {
+        let (mut old_r, mut r) = (self.value, self.order);
+        let (mut old_s, mut s) = (BigInteger::one(), BigInteger::zero());
+        while !r.is_zero() {
+            let quotient = old_r / r;
+            (old_r, r) = (r, old_r - quotient * r);
+            (old_s, s) = (s, old_s - quotient * s);
+        }
+        match old_r == BigInteger::one() {
+            true => Some(Self::new(old_s, self.order)),
+            false => None,
+        }
+    }
+
+    /// self^exponent por "square and multiply".
+    pub fn pow<E: Into<BigInteger>>(&self, exponent: E) -> Self {
+        let exponent = exponent.into();
+        let mut result = Self::new(1, self.order);
+        for i in (0..exponent.bits()).rev() {
+            result = result * result;
+            if exponent.bit(i) {
+                result = result * *self;
+            }
+        }
+        result
+    }
+
+    fn with_value(&self, value: BigInteger) -> Self {
+        Self::new(value, self.order)
+    }
+
+    fn check_order(&self, other: &Self) {
+        if self.order != other.order {
+            panic!("Scalars of different orders");
+        }
+    }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.check_order(&other);
+        self.with_value(self.value + other.value)
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.check_order(&other);
+        self.with_value(self.value - other.value)
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check_order(&other);
+        self.with_value(self.value * other.value)
+    }
+}
+
+impl Div for Scalar {
+    type Output = Self;
+
+    /// self * other^-1. Panics si other no es inversible.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self.check_order(&other);
+        self * other.inverse().expect("the scalar is not invertible")
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.with_value(-self.value)
+    }
+}
+
+impl From<Scalar> for BigInteger {
+    fn from(scalar: Scalar) -> Self {
+        scalar.value
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.order)
+    }
+}
+
+impl<T> Mul<Scalar> for PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    /// k * P con k en Z/nZ. El punto tiene que tener orden n (o un divisor de n).
+    fn mul(self, k: Scalar) -> Self::Output {
+        self.mul_scalar(k.value)
+    }
+}
+
+impl<T> Mul<PointEllipticCurve<T>> for Scalar
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = PointEllipticCurve<T>;
+
+    fn mul(self, point: PointEllipticCurve<T>) -> Self::Output {
+        point.mul_scalar(self.value)
+    }
+}
+
+#[cfg(test)]
+mod test_scalar {
+    use crate::elliptic_curve::{
+        msm::msm,
+        named_curves::{SECP256K1, TP1_P1021},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_01_arithmetic_matches_integers() {
+        let n = 1039;
+        for (a, b) in [(0, 5), (700, 600), (1038, 1038), (-3, 2000), (5, -12)] {
+            let (x, y) = (Scalar::new(a, n), Scalar::new(b, n));
+            let reduce = |v: i64| BigInteger::from(((v % n) + n) % n);
+            assert_eq!((x + y).value(), reduce(a + b));
+            assert_eq!((x - y).value(), reduce(a - b));
+            assert_eq!((x * y).value(), reduce(a * b));
+            assert_eq!((-x).value(), reduce(-a));
+        }
+        assert_eq!(Scalar::new(2, 1039).pow(1038), Scalar::new(1, 1039));
+    }
+
+    #[test]
+    fn test_02_inverse() {
+        let n = 1039;
+        for value in 1..n {
+            let x = Scalar::new(value, n);
+            assert_eq!(x * x.inverse().unwrap(), Scalar::new(1, n));
+            assert_eq!(Scalar::new(1, n) / x, x.inverse().unwrap());
+        }
+        assert!(Scalar::new(0, n).inverse().is_none());
+
+        // n = 21 no es primo: solo los coprimos con 21 tienen inverso
+        assert!(Scalar::new(3, 21).inverse().is_none());
+        assert_eq!(Scalar::new(2, 21).inverse().unwrap(), Scalar::new(11, 21));
+    }
+
+    #[test]
+    fn test_03_random() {
+        let mut rng = rand::thread_rng();
+        let n = SECP256K1.domain_parameters::<BigInteger>().unwrap().n;
+        for _ in 0..100 {
+            let scalar = Scalar::random(n, &mut rng);
+            assert!(scalar.value() < n);
+        }
+
+        // todos los valores de Z/13Z aparecen
+        let mut seen = [false; 13];
+        for _ in 0..1000 {
+            let scalar = Scalar::random_nonzero(13, &mut rng);
+            seen[i32::try_from(scalar.value()).unwrap() as usize] = true;
+        }
+        assert!(!seen[0] && seen[1..].iter().all(|s| *s));
+    }
+
+    #[test]
+    #[should_panic(expected = "Scalars of different orders")]
+    fn test_04_different_orders() {
+        let _ = Scalar::new(1, 13) + Scalar::new(1, 17);
+    }
+
+    #[test]
+    fn test_05_scalar_multiplication() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let (a, b) = (Scalar::new(655, params.n), Scalar::new(-17, params.n));
+        assert_eq!(params.g * a, params.g * 655_usize);
+        assert_eq!(b * params.g, -(params.g * 17_usize));
+        assert_eq!((a * b) * params.g, a * (b * params.g));
+        assert_eq!(params.g.mul_ladder_mod(a, params.n), params.g * a);
+    }
+
+    #[test]
+    fn test_06_signature_equations() {
+        // ECDSA sobre secp256k1: s = k^-1 (z + r d), y la verificacion recupera R = u1 G + u2 Q
+        let mut rng = rand::thread_rng();
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let d = Scalar::random_nonzero(params.n, &mut rng);
+        let q = d * params.g;
+        let z = Scalar::new(BigInteger::from(0x5eed_u64), params.n);
+
+        let k = Scalar::random_nonzero(params.n, &mut rng);
+        let r = Scalar::new((k * params.g).x().unwrap().value, params.n);
+        let s = k.inverse().unwrap() * (z + r * d);
+
+        let w = s.inverse().unwrap();
+        let (u1, u2) = (z * w, r * w);
+        let recovered = msm(&[(u1, params.g), (u2, q)]).unwrap();
+        assert_eq!(Scalar::new(recovered.x().unwrap().value, params.n), r);
+    }
+}
@@ -0,0 +1,442 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, BigInt, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{
+    curve::has_exact_division, jacobian_point::JacobianPoint,
+    point_elliptic_curve::PointEllipticCurve,
+};
+
+impl<T> PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// k * P con "double and add" de izquierda a derecha: log2(k) duplicaciones y a lo sumo otras
+    /// tantas sumas, en coordenadas jacobianas. Si k es negativo el resultado es -(|k| * P).
+    ///
+    /// Si T no es un cuerpo (coordenadas enteras, ver `has_exact_division`) se suma |k| veces con
+    /// las formulas afines: con division truncada solo asi el resultado es P + P + ... + P.
+    pub fn mul_scalar<K: Into<BigInteger>>(&self, k: K) -> Self {
+        let k = k.into();
+        if !has_exact_division(self.a) {
+            return self.mul_repeated(k);
+        }
+        let magnitude = k.abs();
+
+        let mut result = JacobianPoint::identity(self.a, self.b);
+        for i in (0..magnitude.bits()).rev() {
+            result = result.double();
+            if magnitude.bit(i) {
+                result = result.add_mixed(self);
+            }
+        }
+
+        let result = result.to_affine();
+        match k.is_negative() {
+            true => -result,
+            false => result,
+        }
+    }
+
+    // |k| sumas afines, cada una verificada por `Add`; panics si alguna sale de la curva
+    fn mul_repeated(&self, k: BigInteger) -> Self {
+        let mut result = PointEllipticCurve::new_inf(self.a, self.b);
+        let mut remaining = k.abs();
+        while !remaining.is_zero() {
+            result = (result + *self).expect("The point is not on the curve");
+            remaining = remaining - BigInteger::one();
+        }
+        match k.is_negative() {
+            true => -result,
+            false => result,
+        }
+    }
+
+    /// k * P reduciendo antes k modulo el orden del punto (o del grupo): como order * P = O, el
+    /// resultado es el mismo pero la cantidad de operaciones queda acotada por log2(order).
+    pub fn mul_scalar_mod<K: Into<BigInteger>, N: Into<BigInteger>>(&self, k: K, order: N) -> Self {
+        let order = order.into();
+        let k = ((k.into() % order) + order) % order;
+        self.mul_scalar(k)
+    }
+}
+
+impl<X> PointEllipticCurve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + From<u8>,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// k * P con la escalera de Montgomery, para escalares secretos. Hace exactamente `bits`
+    /// iteraciones (una suma y una duplicacion cada una) y elige los operandos con intercambios
+    /// condicionales, asi la secuencia de operaciones de grupo no depende de los bits de k. Se toma |k|
+    /// modulo 2^bits.
+    ///
+    /// No es una implementacion de tiempo constante: solo se iguala la cantidad de operaciones. Las
+    /// sumas jacobianas bifurcan cuando un operando es O o cuando los dos puntos coinciden, y la
+    /// aritmetica de `BigInteger` y de `FiniteBody` tarda segun los valores.
+    ///
+    /// Mientras el acumulador sea O (los ceros a la izquierda de k) las sumas toman un atajo; si se
+    /// conoce el orden del punto conviene `mul_ladder_mod`, que evita ese caso.
+    pub fn mul_ladder<K: Into<BigInteger>>(&self, k: K, bits: usize) -> Self {
+        let k = k.into();
+        let r0 = JacobianPoint::identity(self.a, self.b);
+        let r1 = JacobianPoint::from_affine(self);
+        let result = ladder(r0, r1, &k.abs(), bits).to_affine();
+        match k.is_negative() {
+            true => -result,
+            false => result,
+        }
+    }
+
+    /// Escalera de Montgomery con k reducido modulo el orden n del punto (se necesita n * P = O).
+    ///
+    /// Se usa k' = (k mod n) + n o k' = (k mod n) + 2n, el que tenga bits(n) + 1 bits: k' * P = k * P y
+    /// el bit mas alto de k' siempre es 1, asi que la escalera arranca en (P, 2P) y hace bits(n)
+    /// iteraciones para cualquier k. Los acumuladores pasan por el punto del infinito solo si un
+    /// prefijo m de k' cumple m = n o m + 1 = n, es decir con k = -2, -1, 0 o 1 mod n (con k = 0,
+    /// k' = 2n y su prefijo n da r0 = O en el ultimo paso).
+    pub fn mul_ladder_mod<K: Into<BigInteger>, N: Into<BigInteger>>(&self, k: K, order: N) -> Self {
+        let order = order.into();
+        let bits = order.bits();
+        let k = ((k.into() % order) + order) % order + order;
+        // si k todavia tiene bits(n) bits se usa k + n, elegido con una mascara y no con un if
+        let k = BigInteger::conditional_select(&(k + order), &k, k.bit(bits));
+
+        let r0 = JacobianPoint::from_affine(self);
+        let r1 = r0.double();
+        ladder(r0, r1, &k, bits).to_affine()
+    }
+}
+
+// Invariante: r1 = r0 + P. Por cada bit (del mas alto al mas bajo) se pasa de (r0, r1) = (mP, (m+1)P)
+// a (2mP, (2m+1)P) si el bit es 0 o a ((2m+1)P, (2m+2)P) si es 1. Los intercambios se acumulan: solo
+// se intercambia cuando el bit cambia respecto del anterior, con el 0 o 1 de `FiniteBody::from_bit`.
+fn ladder<X>(
+    mut r0: JacobianPoint<FiniteBody<X>>,
+    mut r1: JacobianPoint<FiniteBody<X>>,
+    k: &BigInteger,
+    bits: usize,
+) -> JacobianPoint<FiniteBody<X>>
+where
+    X: Copy + From<u8>,
+    FiniteBody<X>: Add<FiniteBody<X>>
+        + Div<Output = FiniteBody<X>>
+        + Sub<Output = FiniteBody<X>>
+        + Neg<Output = FiniteBody<X>>
+        + Mul<isize, Output = FiniteBody<X>>
+        + Pow<usize, Output = FiniteBody<X>>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    let p = r0.a.p;
+    let mut swapped = false;
+    for i in (0..bits).rev() {
+        let bit = k.bit(i);
+        r0.conditional_swap(&mut r1, FiniteBody::from_bit(p, swapped ^ bit));
+        swapped = bit;
+        r1 = r0.add_jacobian(&r1);
+        r0 = r0.double();
+    }
+    r0.conditional_swap(&mut r1, FiniteBody::from_bit(p, swapped));
+    r0
+}
+
+macro_rules! impl_mul_signed {
+    ($($t:ty),*) => {
+        $(
+            impl<T> Mul<$t> for PointEllipticCurve<T>
+            where
+                T: Add<T>
+                    + Div<Output = T>
+                    + Sub<Output = T>
+                    + Neg<Output = T>
+                    + Mul<isize, Output = T>
+                    + Pow<usize, Output = T>
+                    + PartialOrd
+                    + Copy
+                    + Zero
+                    + One,
+            {
+                type Output = Self;
+
+                /// k * P con k con signo: un k negativo da -(|k| * P).
+                fn mul(self, k: $t) -> Self::Output {
+                    self.mul_scalar(k)
+                }
+            }
+        )*
+    };
+}
+
+impl_mul_signed!(i32, i64, isize, u64, BigInteger);
+
+// k * P con el escalar a la izquierda, como se escribe en papel.
+macro_rules! impl_scalar_mul_point {
+    ($($t:ty),*) => {
+        $(
+            impl<T> Mul<PointEllipticCurve<T>> for $t
+            where
+                T: Add<T>
+                    + Div<Output = T>
+                    + Sub<Output = T>
+                    + Neg<Output = T>
+                    + Mul<isize, Output = T>
+                    + Pow<usize, Output = T>
+                    + PartialOrd
+                    + Copy
+                    + Zero
+                    + One,
+            {
+                type Output = PointEllipticCurve<T>;
+
+                fn mul(self, point: PointEllipticCurve<T>) -> Self::Output {
+                    point.mul_scalar(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_mul_point!(usize, i32, i64, isize, u64, BigInteger);
+
+impl<T> Mul<BigInt> for PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Self;
+
+    /// k * P con un entero de precision arbitraria. Panics si k no entra en un `BigInteger`; en ese
+    /// caso conviene reducirlo antes modulo el orden del punto.
+    fn mul(self, k: BigInt) -> Self::Output {
+        match BigInteger::try_from(&k) {
+            Ok(k) => self.mul_scalar(k),
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+impl<T, X> Mul<FiniteBody<X>> for PointEllipticCurve<T>
+where
+    T: Add<T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<isize, Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+    X: Into<BigInteger>,
+{
+    type Output = Self;
+
+    /// k * P con k un elemento de Z/nZ, donde n (el modulo de k) es el orden del punto.
+    fn mul(self, k: FiniteBody<X>) -> Self::Output {
+        self.mul_scalar_mod(k.value, k.p)
+    }
+}
+
+#[cfg(test)]
+mod test_scalar_multiplication {
+    use num::Num;
+
+    use crate::elliptic_curve::named_curves::{
+        DomainParameters, SECP256K1, SECP256R1, SECP521R1, TP1_P1021, TP1_P223, TP1_P43,
+    };
+
+    use super::*;
+
+    fn hex(value: &str) -> BigInteger {
+        BigInteger::from_str_radix(value, 16).unwrap()
+    }
+
+    fn assert_kg(params: &DomainParameters<BigInteger>, k: &str, x: &str, y: &str) {
+        let expected = params
+            .curve
+            .point(
+                FiniteBody::new(params.p, hex(x)),
+                FiniteBody::new(params.p, hex(y)),
+            )
+            .unwrap();
+        assert_eq!(params.g * hex(k), expected);
+    }
+
+    #[test]
+    fn test_01_matches_repeated_addition() {
+        let params = TP1_P223.domain_parameters::<i32>().unwrap();
+        let mut expected = params.curve.identity();
+        for k in 0..50_i32 {
+            assert_eq!(params.g.mul_scalar(k), expected);
+            expected = (expected + params.g).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_02_negative_scalars() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        for k in [1_i64, 2, 655, 1038] {
+            let kg = params.g * k;
+            assert_eq!(params.g * -k, -kg);
+            assert_eq!((kg + params.g * -k).unwrap(), params.curve.identity());
+        }
+        assert_eq!(params.g.mul_scalar(0_isize), params.curve.identity());
+        assert_eq!(params.g * -655_isize, params.g * (1039 - 655) as usize);
+    }
+
+    #[test]
+    fn test_03_reduction_mod_order() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let n = params.n;
+        assert_eq!(params.g.mul_scalar_mod(655 + 7 * n, n), params.g * 655);
+        assert_eq!(params.g.mul_scalar_mod(-655, n), params.g * (n - 655));
+        // elemento del cuerpo de escalares Z/nZ
+        assert_eq!(params.g * FiniteBody::new(n, 655 + n), params.g * 655);
+        assert_eq!(params.g * FiniteBody::new(n, -1), -params.g);
+    }
+
+    #[test]
+    fn test_04_order_of_named_generators() {
+        for named_curve in [&SECP256K1, &SECP256R1] {
+            let params = named_curve.domain_parameters::<BigInteger>().unwrap();
+            let n = params.n;
+            assert_eq!(params.g * n, params.curve.identity());
+            assert_eq!(params.g * (n - BigInteger::one()), -params.g);
+            assert_eq!(params.g * (n + BigInteger::one()), params.g);
+        }
+    }
+
+    #[test]
+    fn test_05_known_answers_big_scalars() {
+        // valores calculados con la libreria cryptography de Python
+        assert_kg(
+            &SECP256K1.domain_parameters().unwrap(),
+            "d3b9c9d9a754ac3e9f3344d507b07fa39c6ab7104a08c720cede24428a013fda",
+            "925eb49f4c9790e306a38ebd57dcfc331bafd2831a810d16a0cd9d91d213c2aa",
+            "95554c63f087889613afaad3ac828368b7be7d7f5112182e75cbae85455e94dc",
+        );
+        assert_kg(
+            &SECP256R1.domain_parameters().unwrap(),
+            "22527dbda43e7740604d45f265aec90a0c68ec5541dce77ffa17fea535c3212d",
+            "ccb327555f8eaba64d6efa6e602c921f4595c2f55a5adb6c4f7c4f5b992f12f8",
+            "ab615f7b624f95f7aeec3bf9b32610c5abd4f6734bd5ea00d035045b1ff6a887",
+        );
+        assert_kg(&SECP521R1.domain_parameters().unwrap(), "132d0ebe5c2d786efe5e895dea028ef45e0fff91e751298deb0069e277d3e1ffbe3eeb1c97efe1932a285b94cdffe55088b01f282cb7627070a14d138affd22bb42", "2fc64df58de77ba8e34ff91123f6ec75640bbd1994ca8ae916b8f5899108adeeca621252d0198e093f11489606902d5c2f1822cf117a5f7ced79ab0e5b13d93963", "6c4b44748e394408bfccce506dcad828accb5d527c89efd6cbf1791e0cfe20ee80abd460e89a23378b0c4d161f6e88c07522ec3611c06bffa5a5dcd064054cb289");
+    }
+
+    #[test]
+    fn test_06_num_bigint_scalars() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let k = BigInt::from_str_radix(
+            "d3b9c9d9a754ac3e9f3344d507b07fa39c6ab7104a08c720cede24428a013fda",
+            16,
+        )
+        .unwrap();
+        assert_eq!(
+            params.g * k.clone(),
+            params.g * BigInteger::try_from(&k).unwrap()
+        );
+        assert_eq!(params.g * -k.clone(), -(params.g * k));
+    }
+
+    #[test]
+    fn test_07_ladder_matches_double_and_add() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let n = params.n;
+        for k in (0..3 * n).step_by(7) {
+            let expected = params.g * k;
+            assert_eq!(params.g.mul_ladder(k, 12), expected, "k = {}", k);
+            assert_eq!(params.g.mul_ladder_mod(k, n), expected, "k = {}", k);
+        }
+        assert_eq!(params.g.mul_ladder(-655, 12), params.g * -655);
+        assert_eq!(params.g.mul_ladder_mod(-655, n), params.g * -655);
+    }
+
+    #[test]
+    fn test_08_ladder_on_points_outside_the_subgroup() {
+        // #E = 39 = 3 * 13: (13, 15) tiene orden 13, pero hay puntos de orden 3 y 39
+        let params = TP1_P43.domain_parameters::<i32>().unwrap();
+        let points: Vec<_> = (0..43)
+            .flat_map(|x| (0..43).map(move |y| (x, y)))
+            .filter_map(|(x, y)| {
+                params
+                    .curve
+                    .point(FiniteBody::new(43, x), FiniteBody::new(43, y))
+            })
+            .collect();
+        for point in points {
+            for k in 0..80 {
+                assert_eq!(point.mul_ladder(k, 7), point * k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_09_ladder_known_answers() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let k = hex("d3b9c9d9a754ac3e9f3344d507b07fa39c6ab7104a08c720cede24428a013fda");
+        let expected = params.g * k;
+        assert_eq!(params.g.mul_ladder(k, 256), expected);
+        assert_eq!(params.g.mul_ladder_mod(k, params.n), expected);
+        assert_eq!(
+            params.g.mul_ladder_mod(params.n, params.n),
+            params.curve.identity()
+        );
+        assert_eq!(
+            params
+                .g
+                .mul_ladder_mod(params.n - BigInteger::one(), params.n),
+            -params.g
+        );
+    }
+
+    #[test]
+    fn test_10_scalar_on_the_left() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        assert_eq!(655_usize * params.g, params.g * 655_usize);
+        assert_eq!(-655_i64 * params.g, -(params.g * 655_i64));
+        assert_eq!(BigInteger::from(655) * params.g, params.g * 655);
+        assert_eq!(params.n * params.g, params.curve.identity());
+    }
+}
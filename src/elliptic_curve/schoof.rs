@@ -0,0 +1,408 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{
+    big_integer::BigInteger,
+    finite_body::{finite_body::FiniteBody, polynomial::Polynomial},
+};
+
+use super::curve::{small_multiple, Curve};
+
+impl<X> Curve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>,
+{
+    /// #E(F_p) con el algoritmo de Schoof, en tiempo polinomial en log(p). #E = p + 1 - t, con t la
+    /// traza del Frobenius φ(x, y) = (x^p, y^p), que cumple φ^2 - tφ + p = 0 y |t| <= 2 sqrt(p). Se
+    /// calcula t mod l para primos chicos l (ver `trace_mod`) hasta que su producto supera 4 sqrt(p)
+    /// y se reconstruye t con el teorema chino del resto.
+    ///
+    /// Los tests comparan #E completo solo sobre una curva de 61 bits; con las curvas con nombre
+    /// (secp192k1, secp256k1) son tests `#[ignore]` porque tardan demasiado.
+    pub fn order_schoof(&self) -> BigInteger {
+        let p: BigInteger = self.a.p.into();
+        let sixteen_p = p * BigInteger::from(16);
+
+        let (mut trace, mut modulus) = (BigInteger::zero(), BigInteger::one());
+        let mut l = 2;
+        while modulus * modulus <= sixteen_p {
+            if BigInteger::from(l) != p {
+                trace = crt(trace, modulus, self.trace_mod(l), l);
+                modulus = modulus * BigInteger::from(l);
+            }
+            l = next_prime(l);
+        }
+
+        // el representante de t en (-M/2, M/2]
+        if trace + trace > modulus {
+            trace = trace - modulus;
+        }
+        p + BigInteger::one() - trace
+    }
+
+    /// t mod l, con t la traza del Frobenius y l un primo distinto de p.
+    ///
+    /// Para l = 2, t es par si y solo si E tiene un punto de orden 2, es decir si x^3 + ax + b tiene
+    /// una raiz en F_p: gcd(x^p - x, x^3 + ax + b) != 1.
+    ///
+    /// Para l impar se trabaja con un punto P = (x, y) generico de orden l, en el anillo
+    /// F_p[x, y] / (f_l(x), y^2 - x^3 - ax - b) con f_l el polinomio de division. Con q = p mod l,
+    /// φ^2(P) + q P = t φ(P), asi que t mod l es el j con φ^2(P) + q P = j φ(P). Como P tiene orden
+    /// l, alcanza con un solo punto: si f_l no es irreducible y alguna comparacion da iguales en unas
+    /// raices y distintos en otras, se sigue con un factor de f_l.
+    pub fn trace_mod(&self, l: u64) -> u64 {
+        let p = self.a.p;
+        if l == 2 {
+            let curve_polynomial = self.curve_polynomial();
+            let x = Polynomial::x(p);
+            let x_p = x.pow_mod(p, &curve_polynomial);
+            return match (&x_p - &x).gcd(&curve_polynomial).degree() {
+                Some(0) => 1,
+                _ => 0,
+            };
+        }
+
+        let mut modulus = self.division_polynomial(l as usize).monic();
+        loop {
+            match TorsionRing::new(self, modulus).trace_mod(l) {
+                Ok(trace) => return trace,
+                Err(Split(factor)) => modulus = factor,
+            }
+        }
+    }
+
+    // x^3 + ax + b
+    fn curve_polynomial(&self) -> Polynomial<X> {
+        let p = self.a.p;
+        let (zero, one) = (FiniteBody::new(p, X::zero()), FiniteBody::new(p, X::one()));
+        Polynomial::from_coefficients(p, vec![self.b, self.a, zero, one])
+    }
+}
+
+// Factor propio del modulo, cuando un elemento es cero en unas raices del modulo y no en otras
+struct Split<X>(Polynomial<X>);
+
+// Punto (A(x), B(x) y) del anillo, None es el punto del infinito
+type RingPoint<X> = Option<(Polynomial<X>, Polynomial<X>)>;
+
+// F_p[x, y] / (h(x), y^2 - F(x)) con h un factor de un polinomio de division f_l
+struct TorsionRing<'a, X> {
+    curve: &'a Curve<FiniteBody<X>>,
+    modulus: Polynomial<X>,
+    curve_polynomial: Polynomial<X>,
+}
+
+impl<'a, X> TorsionRing<'a, X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>,
+{
+    fn new(curve: &'a Curve<FiniteBody<X>>, modulus: Polynomial<X>) -> Self {
+        let curve_polynomial = &curve.curve_polynomial() % &modulus;
+        TorsionRing {
+            curve,
+            modulus,
+            curve_polynomial,
+        }
+    }
+
+    fn trace_mod(&self, l: u64) -> Result<u64, Split<X>> {
+        let p_value = self.curve.a.p;
+        let p: BigInteger = p_value.into();
+        let q = u64::try_from(p % BigInteger::from(l)).expect("p mod l fits in 64 bits");
+
+        // φ(x, y) = (x^p, y^p) = (x^p, F^((p - 1) / 2) y)
+        let x = &Polynomial::x(p_value) % &self.modulus;
+        let x_p = x.pow_mod(p, &self.modulus);
+        let y_p = self
+            .curve_polynomial
+            .pow_mod((p - BigInteger::one()) >> 1, &self.modulus);
+
+        let point = Some((x, Polynomial::one(p_value)));
+        let frobenius = Some((x_p.clone(), y_p.clone()));
+        // φ^2(P) = (x^(p^2), (y_p)^p y_p y): A(x)^p = A(x^p) con coeficientes en F_p, y elevar a p es
+        // mucho mas barato que componer con x^p por Horner
+        let x2 = x_p.pow_mod(p, &self.modulus);
+        let y2 = &(&y_p.pow_mod(p, &self.modulus) * &y_p) % &self.modulus;
+        let (xq, yq) = self.mul(&point, q)?.expect("q P != O");
+
+        if self.equal(&x2, &xq)? {
+            // φ^2(P) = -q P: t φ(P) = O, t = 0
+            if !self.equal(&y2, &yq)? {
+                return Ok(0);
+            }
+            // φ^2(P) = q P: t φ(P) = 2q P, asi que P es autovector de φ con un autovalor w,
+            // w^2 = q y t = 2w
+            let w = (1..l)
+                .find(|w| w * w % l == q)
+                .expect("q is a square mod l when φ^2(P) = q P");
+            let (_, yw) = self.mul(&point, w)?.expect("w P != O");
+            return Ok(match self.equal(&y_p, &yw)? {
+                true => 2 * w % l,
+                false => (l - 2 * w % l) % l,
+            });
+        }
+
+        let sum = self.add(&Some((x2, y2)), &Some((xq, yq)))?;
+        let (xs, ys) = sum.expect("φ^2(P) != -q P");
+        let mut multiple = frobenius.clone();
+        for j in 1..=(l - 1) / 2 {
+            let (xj, yj) = multiple.as_ref().expect("j φ(P) != O for j < l");
+            if self.equal(&xs, xj)? {
+                return Ok(match self.equal(&ys, yj)? {
+                    true => j,
+                    false => l - j,
+                });
+            }
+            multiple = self.add(&multiple, &frobenius)?;
+        }
+        unreachable!("φ^2(P) + q P is a multiple of φ(P) different from O")
+    }
+
+    fn add(&self, first: &RingPoint<X>, second: &RingPoint<X>) -> Result<RingPoint<X>, Split<X>> {
+        let ((x1, y1), (x2, y2)) = match (first, second) {
+            (None, point) | (point, None) => return Ok(point.clone()),
+            (Some(first), Some(second)) => (first, second),
+        };
+        if self.equal(x1, x2)? {
+            return match self.equal(y1, y2)? {
+                true => self.double(first),
+                false => Ok(None),
+            };
+        }
+        let slope = &(y2 - y1) * &self.inverse(&(x2 - x1))?;
+        Ok(self.chord(x1, y1, x2, &slope))
+    }
+
+    fn double(&self, point: &RingPoint<X>) -> Result<RingPoint<X>, Split<X>> {
+        let (x1, y1) = match point {
+            None => return Ok(None),
+            Some(point) => point,
+        };
+        // λ = (3x^2 + a) / (2y) = (3x^2 + a) / (2B F) * y
+        let p = self.modulus.p();
+        let one = FiniteBody::new(p, X::one());
+        let numerator =
+            &(x1 * x1).scale(small_multiple(one, 3)) + &Polynomial::constant(self.curve.a);
+        let denominator =
+            (&(y1 * &self.curve_polynomial) % &self.modulus).scale(small_multiple(one, 2));
+        let slope = &numerator * &self.inverse(&denominator)?;
+        Ok(self.chord(x1, y1, x1, &slope))
+    }
+
+    // Con λ = L y: x3 = L^2 F - x1 - x2, y3 = (L (x1 - x3) - B1) y
+    fn chord(
+        &self,
+        x1: &Polynomial<X>,
+        y1: &Polynomial<X>,
+        x2: &Polynomial<X>,
+        slope: &Polynomial<X>,
+    ) -> RingPoint<X> {
+        let slope = slope % &self.modulus;
+        let slope_squared = &(&slope * &slope) % &self.modulus;
+        let x3 = &(&(&(&slope_squared * &self.curve_polynomial) - x1) - x2) % &self.modulus;
+        let y3 = &(&(&slope * &(x1 - &x3)) - y1) % &self.modulus;
+        Some((x3, y3))
+    }
+
+    fn mul(&self, point: &RingPoint<X>, k: u64) -> Result<RingPoint<X>, Split<X>> {
+        let mut result = None;
+        for i in (0..u64::BITS - k.leading_zeros()).rev() {
+            result = self.double(&result)?;
+            if (k >> i) & 1 == 1 {
+                result = self.add(&result, point)?;
+            }
+        }
+        Ok(result)
+    }
+
+    // a = b en todas las raices del modulo, en ninguna, o un Split con las raices donde son iguales
+    fn equal(&self, a: &Polynomial<X>, b: &Polynomial<X>) -> Result<bool, Split<X>> {
+        let difference = &(a - b) % &self.modulus;
+        if difference.is_zero() {
+            return Ok(true);
+        }
+        let gcd = difference.gcd(&self.modulus);
+        match gcd.degree() {
+            Some(0) => Ok(false),
+            _ => Err(Split(gcd)),
+        }
+    }
+
+    // Inverso modulo h, o un Split con las raices de h donde a se anula
+    fn inverse(&self, a: &Polynomial<X>) -> Result<Polynomial<X>, Split<X>> {
+        match a.inverse_mod(&self.modulus) {
+            Some(inverse) => Ok(inverse),
+            None => {
+                let gcd = a.gcd(&self.modulus);
+                assert!(
+                    gcd.degree() < self.modulus.degree(),
+                    "division by zero in the torsion ring"
+                );
+                Err(Split(gcd))
+            }
+        }
+    }
+}
+
+// t' con t' = t mod m y t' = r mod l (l primo que no divide a m), en [0, m * l)
+fn crt(t: BigInteger, m: BigInteger, r: u64, l: u64) -> BigInteger {
+    let small = |value: BigInteger| {
+        u64::try_from(value % BigInteger::from(l)).expect("a value mod l fits in 64 bits")
+    };
+    let (t_mod_l, m_mod_l) = (small(t), small(m));
+    // m^-1 mod l por el pequeño teorema de Fermat
+    let m_inverse = pow_mod(m_mod_l, l - 2, l);
+    let k = (r + l - t_mod_l) % l * m_inverse % l;
+    t + m * BigInteger::from(k)
+}
+
+fn pow_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let (mut result, mut base, mut exponent) = (1_u128, base as u128, exponent);
+    let modulus = modulus as u128;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+fn next_prime(n: u64) -> u64 {
+    (n + 1..)
+        .find(|candidate| {
+            (2..)
+                .take_while(|d| d * d <= *candidate)
+                .all(|d| candidate % d != 0)
+        })
+        .expect("there are infinitely many primes")
+}
+
+#[cfg(test)]
+mod test_schoof {
+    use crate::elliptic_curve::named_curves::{SECP192K1, SECP256K1, SECP256R1};
+
+    use super::*;
+
+    // y^2 = x^3 - 3x + 111 sobre F_p con p = 2^61 - 1, de orden primo n y G = (1, TEST_P61_GY). Como
+    // p^2 entra en `i128`, el algoritmo de Schoof completo corre en segundos.
+    const TEST_P61_P: i128 = (1 << 61) - 1;
+    const TEST_P61_GY: i128 = 509478702933351334;
+    const TEST_P61_N: i128 = 0x200000005fa10505;
+
+    #[test]
+    fn test_01_matches_count_points() {
+        for (p, a, b) in [
+            (43_i64, 0, 6),
+            (223, 0, 7),
+            (1021, -3, -3),
+            (1021, 905, 100),
+            (4099, 1, 0),
+            (4099, 0, 7),
+            (10007, 123, 456),
+        ] {
+            let curve = Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap();
+            assert_eq!(
+                curve.order_schoof(),
+                BigInteger::from(curve.count_points()),
+                "p = {}, a = {}, b = {}",
+                p,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_02_trace_mod_small_primes() {
+        let p = 1021_i64;
+        for (a, b) in [(-3, -3), (2, 3), (0, 5), (7, 0)] {
+            let curve = Curve::new(FiniteBody::new(p, a), FiniteBody::new(p, b)).unwrap();
+            let trace = p + 1 - curve.count_points() as i64;
+            for l in [2, 3, 5, 7, 11, 13] {
+                assert_eq!(curve.trace_mod(l) as i64, trace.rem_euclid(l as i64));
+            }
+        }
+    }
+
+    #[test]
+    fn test_03_matches_mestre() {
+        let mut rng = rand::thread_rng();
+        let p: i128 = (1 << 32) + 15;
+        let curve = Curve::new(FiniteBody::new(p, 3), FiniteBody::new(p, 11)).unwrap();
+        assert_eq!(curve.order_schoof(), curve.order_mestre(&mut rng));
+    }
+
+    #[test]
+    fn test_04_named_curves_trace_mod() {
+        // #E = n * h, asi que t = p + 1 - n * h
+        for named_curve in [&SECP192K1, &SECP256K1, &SECP256R1] {
+            let params = named_curve.domain_parameters::<BigInteger>().unwrap();
+            let p = params.curve.a.p;
+            let trace = p + BigInteger::one() - params.n * params.h;
+            for l in [2_u64, 3, 5] {
+                let l_big = BigInteger::from(l);
+                let expected = ((trace % l_big) + l_big) % l_big;
+                assert_eq!(
+                    BigInteger::from(params.curve.trace_mod(l)),
+                    expected,
+                    "{}, l = {}",
+                    named_curve.name,
+                    l
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_05_full_order_of_test_curve() {
+        let p = TEST_P61_P;
+        let curve = Curve::new(FiniteBody::new(p, -3), FiniteBody::new(p, 111)).unwrap();
+        let g = curve
+            .point(FiniteBody::new(p, 1), FiniteBody::new(p, TEST_P61_GY))
+            .unwrap();
+        let order = curve.order_schoof();
+        assert_eq!(order, BigInteger::from(TEST_P61_N));
+        assert_eq!(g.mul_scalar(order), curve.identity());
+    }
+
+    // Con p de 192 o 256 bits el Schoof completo tarda demasiado para cada corrida:
+    // `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_06_full_order_secp192k1() {
+        let params = SECP192K1.domain_parameters::<BigInteger>().unwrap();
+        assert_eq!(params.curve.order_schoof(), params.n * params.h);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_07_full_order_secp256k1() {
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        assert_eq!(params.curve.order_schoof(), params.n * params.h);
+    }
+}
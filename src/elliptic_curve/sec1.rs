@@ -0,0 +1,238 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::{big_integer::BigInteger, finite_body::finite_body::FiniteBody};
+
+use super::{curve::Curve, error::EllipticCurveError, point_elliptic_curve::PointEllipticCurve};
+
+const INFINITY: u8 = 0x00;
+const COMPRESSED_EVEN: u8 = 0x02;
+const COMPRESSED_ODD: u8 = 0x03;
+const UNCOMPRESSED: u8 = 0x04;
+
+impl<X> PointEllipticCurve<FiniteBody<X>>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>
+        + TryFrom<BigInteger>,
+{
+    /// Elliptic-Curve-Point-to-Octet-String (SEC 1, 2.3.3): 0x00 para el punto del infinito,
+    /// 0x04 || x || y sin comprimir y 0x02 / 0x03 || x comprimido, segun la paridad de y. Las
+    /// coordenadas ocupan ceil(log2(p) / 8) bytes.
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        let (x, y) = match self.coordinates() {
+            Some((x, y)) => (x, y),
+            None => return vec![INFINITY],
+        };
+        let len = field_len(x.p);
+        let y_value: BigInteger = y.value.into();
+
+        let mut bytes = Vec::with_capacity(1 + 2 * len);
+        match compressed {
+            true if y_value.is_odd() => bytes.push(COMPRESSED_ODD),
+            true => bytes.push(COMPRESSED_EVEN),
+            false => bytes.push(UNCOMPRESSED),
+        }
+        bytes.extend(x.value.into().to_bytes_be_padded(len));
+        if !compressed {
+            bytes.extend(y_value.to_bytes_be_padded(len));
+        }
+        bytes
+    }
+
+    /// Octet-String-to-Elliptic-Curve-Point (SEC 1, 2.3.4) sobre `curve`. Para los puntos
+    /// comprimidos se recupera y como la raiz cuadrada de x^3 + ax + b con la paridad indicada.
+    /// Falla si el largo o el prefijo no son validos, si alguna coordenada no es menor que p o si el
+    /// punto no esta en la curva.
+    pub fn from_sec1(
+        bytes: &[u8],
+        curve: &Curve<FiniteBody<X>>,
+    ) -> Result<Self, EllipticCurveError> {
+        let p = curve.a.p;
+        let len = field_len(p);
+        let element = |bytes: &[u8]| -> Result<FiniteBody<X>, EllipticCurveError> {
+            let value = BigInteger::from_bytes_be(bytes);
+            if value >= p.into() {
+                return Err(EllipticCurveError::CoordinateOutOfRange);
+            }
+            match X::try_from(value) {
+                Ok(value) => Ok(FiniteBody::new(p, value)),
+                Err(_) => Err(EllipticCurveError::CoordinateOutOfRange),
+            }
+        };
+
+        match (bytes.first(), bytes.len()) {
+            (Some(&INFINITY), 1) => Ok(curve.identity()),
+            (Some(&UNCOMPRESSED), l) if l == 1 + 2 * len => {
+                let x = element(&bytes[1..1 + len])?;
+                let y = element(&bytes[1 + len..])?;
+                PointEllipticCurve::try_new(x, y, curve.a, curve.b)
+            }
+            (Some(&prefix), l)
+                if l == 1 + len && (prefix == COMPRESSED_EVEN || prefix == COMPRESSED_ODD) =>
+            {
+                let x = element(&bytes[1..])?;
+                let y_squared = x * x * x + curve.a * x + curve.b;
+                let y = y_squared
+                    .sqrt()
+                    .ok_or(EllipticCurveError::PointNotOnCurve)?;
+                let odd = prefix == COMPRESSED_ODD;
+                let y = match y.value.into().is_odd() == odd {
+                    true => y,
+                    false => -y,
+                };
+                // y = 0 solo tiene la paridad par
+                if y.value.into().is_odd() != odd {
+                    return Err(EllipticCurveError::PointNotOnCurve);
+                }
+                PointEllipticCurve::try_new(x, y, curve.a, curve.b)
+            }
+            _ => Err(EllipticCurveError::InvalidEncoding),
+        }
+    }
+}
+
+// ceil(log2(p) / 8)
+fn field_len<X: Into<BigInteger>>(p: X) -> usize {
+    p.into().bits().div_ceil(8)
+}
+
+#[cfg(test)]
+mod test_sec1 {
+    use num::Num;
+
+    use crate::elliptic_curve::named_curves::{SECP256K1, SECP256R1, SECP521R1, TP1_P1021};
+
+    use super::*;
+
+    fn hex(value: &str) -> Vec<u8> {
+        BigInteger::from_str_radix(value, 16).unwrap().to_bytes_be()
+    }
+
+    #[test]
+    fn test_01_secp256k1_generator() {
+        // la forma comprimida de G es la que publica SEC 2
+        let params = SECP256K1.domain_parameters::<BigInteger>().unwrap();
+        let compressed = params.g.to_sec1(true);
+        let mut expected = vec![0x02];
+        expected.extend(hex(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+        assert_eq!(compressed, expected);
+
+        let uncompressed = params.g.to_sec1(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[0], 0x04);
+
+        assert_eq!(
+            PointEllipticCurve::from_sec1(&compressed, &params.curve).unwrap(),
+            params.g
+        );
+        assert_eq!(
+            PointEllipticCurve::from_sec1(&uncompressed, &params.curve).unwrap(),
+            params.g
+        );
+    }
+
+    #[test]
+    fn test_02_round_trip_named_curves() {
+        for named_curve in [&SECP256K1, &SECP256R1, &SECP521R1] {
+            let params = named_curve.domain_parameters::<BigInteger>().unwrap();
+            for k in 1..20_usize {
+                let point = params.g * k;
+                for compressed in [true, false] {
+                    let bytes = point.to_sec1(compressed);
+                    assert_eq!(
+                        PointEllipticCurve::from_sec1(&bytes, &params.curve).unwrap(),
+                        point
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_03_p256_compressed_generator() {
+        // G de P-256 comprimido: la y de G es impar
+        let params = SECP256R1.domain_parameters::<BigInteger>().unwrap();
+        let bytes = hex("036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296");
+        assert_eq!(
+            PointEllipticCurve::from_sec1(&bytes, &params.curve).unwrap(),
+            params.g
+        );
+    }
+
+    #[test]
+    fn test_04_small_curve_and_infinity() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        // p = 1021 ocupa 2 bytes
+        assert_eq!(params.g.to_sec1(false), vec![0x04, 0x01, 0x7b, 0x03, 0xf3]);
+        assert_eq!(params.g.to_sec1(true), vec![0x03, 0x01, 0x7b]);
+        assert_eq!(params.curve.identity().to_sec1(true), vec![0x00]);
+        assert_eq!(
+            PointEllipticCurve::from_sec1(&[0x00], &params.curve).unwrap(),
+            params.curve.identity()
+        );
+
+        for k in 1..1039_usize {
+            let point = params.g * k;
+            let bytes = point.to_sec1(true);
+            assert_eq!(
+                PointEllipticCurve::from_sec1(&bytes, &params.curve).unwrap(),
+                point
+            );
+        }
+    }
+
+    #[test]
+    fn test_05_invalid_encodings() {
+        let params = TP1_P1021.domain_parameters::<i32>().unwrap();
+        let decode = |bytes: &[u8]| PointEllipticCurve::from_sec1(bytes, &params.curve);
+
+        assert_eq!(decode(&[]), Err(EllipticCurveError::InvalidEncoding));
+        assert_eq!(
+            decode(&[0x00, 0x00]),
+            Err(EllipticCurveError::InvalidEncoding)
+        );
+        assert_eq!(
+            decode(&[0x05, 0x01, 0x7b]),
+            Err(EllipticCurveError::InvalidEncoding)
+        );
+        assert_eq!(
+            decode(&[0x04, 0x01, 0x7b, 0x03]),
+            Err(EllipticCurveError::InvalidEncoding)
+        );
+        // x = 1021 = p
+        assert_eq!(
+            decode(&[0x02, 0x03, 0xfd]),
+            Err(EllipticCurveError::CoordinateOutOfRange)
+        );
+        // (379, 1010) no esta en la curva
+        assert_eq!(
+            decode(&[0x04, 0x01, 0x7b, 0x03, 0xf2]),
+            Err(EllipticCurveError::PointNotOnCurve)
+        );
+        // un x para el que x^3 + ax + b no es un cuadrado
+        let x = (0..1021)
+            .find(|x| {
+                let x = FiniteBody::new(1021, *x);
+                (x * x * x + params.curve.a * x + params.curve.b).legendre() == -1
+            })
+            .unwrap();
+        assert_eq!(
+            decode(&[0x02, (x >> 8) as u8, x as u8]),
+            Err(EllipticCurveError::PointNotOnCurve)
+        );
+    }
+}
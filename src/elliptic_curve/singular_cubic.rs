@@ -0,0 +1,252 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::finite_body::finite_body::FiniteBody;
+
+use super::curve::is_singular;
+
+/// Tipo de punto singular de una cubica y^2 = x^3 + ax + b con 4a^3 + 27b^2 = 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingularKind {
+    /// a = b = 0, y^2 = x^3. Los puntos no singulares forman un grupo isomorfo a (F_p, +).
+    Cusp,
+    /// Nodo con las pendientes de las tangentes en F_p: el grupo es isomorfo a F_p^*.
+    SplitNode,
+    /// Nodo con las pendientes en F_p^2: el grupo es isomorfo al subgrupo de norma 1 de F_p^2^*,
+    /// de orden p + 1.
+    NonSplitNode,
+}
+
+/// Imagen de un punto no singular en el grupo aditivo o multiplicativo correspondiente.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SingularImage<T> {
+    Additive(T),
+    /// c + d * sqrt(gamma), de norma c^2 - gamma * d^2 = 1. Si el nodo es "split", sqrt(gamma) esta
+    /// en F_p y este elemento es un elemento de F_p^*.
+    Multiplicative {
+        c: T,
+        d: T,
+    },
+}
+
+/// Cubica singular. Sirve para mostrar por que se rechazan estas curvas: la ley de la cuerda y la
+/// tangente sigue funcionando en los puntos no singulares, pero el grupo resultante es isomorfo a un
+/// grupo donde el logaritmo discreto es facil.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SingularCubic<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> SingularCubic<FiniteBody<T>>
+where
+    T: Add<T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Rem<Output = T>
+        + Pow<usize, Output = T>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// None si la cubica no es singular (en ese caso es una curva eliptica).
+    pub fn new(a: FiniteBody<T>, b: FiniteBody<T>) -> Option<Self> {
+        if !is_singular(a, b) {
+            return None;
+        }
+        Some(SingularCubic { a, b })
+    }
+
+    /// Punto singular (alpha, 0), donde alpha es la raiz multiple de x^3 + ax + b.
+    pub fn singular_point(&self) -> (FiniteBody<T>, FiniteBody<T>) {
+        let zero = FiniteBody::new(self.a.p, T::zero());
+        if self.a.is_zero() {
+            return (zero, zero);
+        }
+        // 3 alpha^2 + a = 0 y alpha^3 + a alpha + b = 0 => alpha = -3b / 2a
+        (-(self.b * 3_isize) / (self.a * 2_isize), zero)
+    }
+
+    pub fn kind(&self) -> SingularKind {
+        if self.a.is_zero() {
+            return SingularKind::Cusp;
+        }
+        let gamma = self.gamma();
+        let p = gamma.p;
+        // criterio de Euler: gamma es un cuadrado si gamma^((p - 1) / 2) = 1
+        let exponent = (p - T::one()) / (T::one() + T::one());
+        if gamma.pow_mod(exponent) == T::one() {
+            SingularKind::SplitNode
+        } else {
+            SingularKind::NonSplitNode
+        }
+    }
+
+    /// Isomorfismo de los puntos no singulares en el grupo aditivo o multiplicativo. El punto del
+    /// infinito va al neutro (0 o 1). None si (x, y) no esta en la cubica o es el punto singular.
+    pub fn map(&self, x: FiniteBody<T>, y: FiniteBody<T>) -> Option<SingularImage<FiniteBody<T>>> {
+        if y.pow(2) != x.pow(3) + self.a * x + self.b {
+            return None;
+        }
+        let (alpha, _) = self.singular_point();
+        let u = x - alpha;
+        if u.is_zero() {
+            return None;
+        }
+
+        if self.a.is_zero() {
+            // cuspide: (x, y) -> x / y
+            return Some(SingularImage::Additive(x / y));
+        }
+
+        // nodo: y^2 = u^2 (u + gamma), las tangentes en el nodo son y = +-sqrt(gamma) u, y el punto va a
+        // (y + sqrt(gamma) u) / (y - sqrt(gamma) u) = (y^2 + gamma u^2 + 2yu sqrt(gamma)) / u^3
+        let gamma = self.gamma();
+        let u3 = u.pow(3);
+        Some(SingularImage::Multiplicative {
+            c: (y.pow(2) + gamma * u.pow(2)) / u3,
+            d: (y * u * 2_isize) / u3,
+        })
+    }
+
+    /// Operacion del grupo de llegada: suma en el caso aditivo, producto en F_p[sqrt(gamma)] en el
+    /// multiplicativo.
+    pub fn operate(
+        &self,
+        first: SingularImage<FiniteBody<T>>,
+        second: SingularImage<FiniteBody<T>>,
+    ) -> Option<SingularImage<FiniteBody<T>>> {
+        match (first, second) {
+            (SingularImage::Additive(t1), SingularImage::Additive(t2)) => {
+                Some(SingularImage::Additive(t1 + t2))
+            }
+            (
+                SingularImage::Multiplicative { c: c1, d: d1 },
+                SingularImage::Multiplicative { c: c2, d: d2 },
+            ) => Some(SingularImage::Multiplicative {
+                c: c1 * c2 + self.gamma() * d1 * d2,
+                d: c1 * d2 + c2 * d1,
+            }),
+            _ => None,
+        }
+    }
+
+    // x^3 + ax + b = (x - alpha)^2 (x + 2 alpha), gamma = 3 alpha
+    fn gamma(&self) -> FiniteBody<T> {
+        let (alpha, _) = self.singular_point();
+        alpha * 3_isize
+    }
+}
+
+#[cfg(test)]
+mod test_singular_cubic {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    type Point = Option<(FiniteBody<i32>, FiniteBody<i32>)>;
+
+    // ley de la cuerda y la tangente, None es el punto del infinito
+    fn add(cubic: &SingularCubic<FiniteBody<i32>>, p1: Point, p2: Point) -> Point {
+        let ((x1, y1), (x2, y2)) = match (p1, p2) {
+            (None, _) => return p2,
+            (_, None) => return p1,
+            (Some(p1), Some(p2)) => (p1, p2),
+        };
+        if x1 == x2 && (y1 + y2).is_zero() {
+            return None;
+        }
+        let s = if x1 == x2 {
+            (x1.pow(2) * 3_isize + cubic.a) / (y1 * 2_isize)
+        } else {
+            (y2 - y1) / (x2 - x1)
+        };
+        let x3 = s.pow(2) - x1 - x2;
+        Some((x3, s * (x1 - x3) - y1))
+    }
+
+    fn nonsingular_points(cubic: &SingularCubic<FiniteBody<i32>>, p: i32) -> Vec<Point> {
+        let mut points = vec![];
+        for x in 0..p {
+            for y in 0..p {
+                let (x, y) = (FiniteBody::new(p, x), FiniteBody::new(p, y));
+                if cubic.map(x, y).is_some() {
+                    points.push(Some((x, y)));
+                }
+            }
+        }
+        points
+    }
+
+    fn check_isomorphism(cubic: &SingularCubic<FiniteBody<i32>>, p: i32, group_order: usize) {
+        let points = nonsingular_points(cubic, p);
+        assert_eq!(points.len() + 1, group_order);
+
+        let image = |point: Point| point.map(|(x, y)| cubic.map(x, y).unwrap());
+        let images: HashSet<_> = points.iter().map(|point| image(*point)).collect();
+        assert_eq!(images.len(), points.len()); // inyectiva
+
+        for p1 in points.iter().step_by(3) {
+            for p2 in points.iter().step_by(5) {
+                let sum = add(cubic, *p1, *p2);
+                let expected = cubic.operate(image(*p1).unwrap(), image(*p2).unwrap());
+                match sum {
+                    Some(_) => assert_eq!(image(sum), expected),
+                    // P + (-P) = O va al neutro
+                    None => assert!(
+                        matches!(
+                            expected.unwrap(),
+                            SingularImage::Additive(t) if t.is_zero()
+                        ) || matches!(
+                            expected.unwrap(),
+                            SingularImage::Multiplicative { c, d } if c == 1 && d.is_zero()
+                        )
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_01_elliptic_curve_is_not_singular() {
+        let p = 43;
+        assert!(SingularCubic::new(FiniteBody::new(p, 0), FiniteBody::new(p, 6)).is_none());
+    }
+
+    #[test]
+    fn test_02_cusp() {
+        let p = 43;
+        let zero = FiniteBody::new(p, 0);
+        let cubic = SingularCubic::new(zero, zero).unwrap();
+
+        assert_eq!(cubic.kind(), SingularKind::Cusp);
+        assert_eq!(cubic.singular_point(), (zero, zero));
+        check_isomorphism(&cubic, p, p as usize);
+    }
+
+    #[test]
+    fn test_03_split_node() {
+        // alpha = 2: y^2 = (x - 2)^2 (x + 4), gamma = 6 es un cuadrado modulo 43
+        let p = 43;
+        let cubic = SingularCubic::new(FiniteBody::new(p, -12), FiniteBody::new(p, 16)).unwrap();
+
+        assert_eq!(cubic.kind(), SingularKind::SplitNode);
+        assert_eq!(cubic.singular_point().0, 2);
+        check_isomorphism(&cubic, p, (p - 1) as usize);
+    }
+
+    #[test]
+    fn test_04_non_split_node() {
+        // alpha = 1: y^2 = (x - 1)^2 (x + 2), gamma = 3 no es un cuadrado modulo 43
+        let p = 43;
+        let cubic = SingularCubic::new(FiniteBody::new(p, -3), FiniteBody::new(p, 2)).unwrap();
+
+        assert_eq!(cubic.kind(), SingularKind::NonSplitNode);
+        assert_eq!(cubic.singular_point().0, 1);
+        check_isomorphism(&cubic, p, (p + 1) as usize);
+    }
+}
@@ -0,0 +1,255 @@
+use num::{One, Zero};
+
+use crate::big_integer::BigInteger;
+
+/// Se divide por los primos menores que esta cota antes de buscar factores con Pollard rho.
+const TRIAL_DIVISION_BOUND: u64 = 1000;
+/// Bases de Miller-Rabin: con los primeros 12 primos el test es exacto para n < 3.3 * 10^24, y para
+/// n mas grandes la probabilidad de error es despreciable.
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Factorizacion de n > 0 como pares (primo, exponente), ordenados por primo. Divide por los primos
+/// chicos y parte el resto con el metodo rho de Pollard (variante de Brent) hasta que cada factor pasa
+/// el test de Miller-Rabin.
+///
+/// Rho encuentra un factor q en O(sqrt(q)) pasos: es rapido para los ordenes de las curvas, que
+/// suelen ser un primo grande por un cofactor chico, pero no para un n con dos factores primos
+/// grandes. Panics si n no entra en 576 bits (los productos modulo n tienen que entrar en un
+/// `BigInteger`).
+pub fn factor(n: BigInteger) -> Vec<(BigInteger, u32)> {
+    assert!(
+        n > BigInteger::zero(),
+        "only positive integers can be factored"
+    );
+    assert!(n.bits() <= 576, "the integer is too large to factor");
+
+    let mut primes = vec![];
+    let mut n = n;
+    for d in (2..TRIAL_DIVISION_BOUND).filter(|d| is_small_prime(*d)) {
+        let d = BigInteger::from(d);
+        while (n % d).is_zero() {
+            primes.push(d);
+            n = n / d;
+        }
+    }
+
+    let mut pending = vec![n];
+    while let Some(m) = pending.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if is_probable_prime(m) {
+            primes.push(m);
+            continue;
+        }
+        let d = pollard_rho(m);
+        pending.push(d);
+        pending.push(m / d);
+    }
+
+    primes.sort();
+    let mut factors: Vec<(BigInteger, u32)> = vec![];
+    for prime in primes {
+        match factors.last_mut() {
+            Some((last, exponent)) if *last == prime => *exponent += 1,
+            _ => factors.push((prime, 1)),
+        }
+    }
+    factors
+}
+
+/// Divisores positivos de n > 0, ordenados de menor a mayor.
+pub fn divisors(n: BigInteger) -> Vec<BigInteger> {
+    let mut divisors = vec![BigInteger::one()];
+    for (prime, exponent) in factor(n) {
+        let previous = divisors.len();
+        let mut power = BigInteger::one();
+        for _ in 0..exponent {
+            power = power * prime;
+            for i in 0..previous {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+    divisors.sort();
+    divisors
+}
+
+/// Test de primalidad de Miller-Rabin con bases fijas (ver `MILLER_RABIN_BASES`).
+pub fn is_probable_prime(n: BigInteger) -> bool {
+    let two = BigInteger::from(2);
+    if n < two {
+        return false;
+    }
+    for base in MILLER_RABIN_BASES {
+        let base = BigInteger::from(base);
+        if n == base {
+            return true;
+        }
+        if (n % base).is_zero() {
+            return false;
+        }
+    }
+
+    // n - 1 = d * 2^s con d impar
+    let n_minus_one = n - BigInteger::one();
+    let mut d = n_minus_one;
+    let mut s = 0;
+    while !d.is_odd() {
+        d = d >> 1;
+        s += 1;
+    }
+
+    MILLER_RABIN_BASES.iter().all(|base| {
+        let mut x = pow_mod(BigInteger::from(*base), d, n);
+        if x.is_one() || x == n_minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = x * x % n;
+            if x == n_minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Maximo comun divisor de |a| y |b|.
+pub fn gcd(a: BigInteger, b: BigInteger) -> BigInteger {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Un factor propio de n compuesto e impar. Brent: x_{i+1} = x_i^2 + c, comparando con potencias de 2
+// y acumulando los productos |x - y| para hacer un gcd cada 100 pasos. Si el gcd da n se prueba con
+// otro c.
+fn pollard_rho(n: BigInteger) -> BigInteger {
+    let one = BigInteger::one();
+    for c in 1.. {
+        let c = BigInteger::from(c);
+        let step = |x: BigInteger| (x * x + c) % n;
+        let (mut x, mut y, mut product) = (BigInteger::from(2), BigInteger::from(2), one);
+        let mut saved = y;
+        let mut divisor = one;
+        let mut power = 1;
+        while divisor.is_one() {
+            x = y;
+            for _ in 0..power {
+                y = step(y);
+            }
+            let mut k = 0;
+            while k < power && divisor.is_one() {
+                saved = y;
+                for _ in 0..100.min(power - k) {
+                    y = step(y);
+                    product = product * (x - y).abs() % n;
+                }
+                divisor = gcd(product, n);
+                k += 100;
+            }
+            power *= 2;
+        }
+        // el gcd acumulado paso de largo: se rehace paso a paso desde el ultimo guardado
+        if divisor == n {
+            loop {
+                saved = step(saved);
+                divisor = gcd((x - saved).abs(), n);
+                if !divisor.is_one() {
+                    break;
+                }
+            }
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!("some c finds a factor")
+}
+
+fn pow_mod(base: BigInteger, exponent: BigInteger, modulus: BigInteger) -> BigInteger {
+    let mut result = BigInteger::one();
+    for i in (0..exponent.bits()).rev() {
+        result = result * result % modulus;
+        if exponent.bit(i) {
+            result = result * base % modulus;
+        }
+    }
+    result
+}
+
+fn is_small_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+#[cfg(test)]
+mod test_factorization {
+    use num::Num;
+
+    use super::*;
+
+    fn big(value: u64) -> BigInteger {
+        BigInteger::from(value)
+    }
+
+    #[test]
+    fn test_01_small_numbers() {
+        assert_eq!(factor(big(1)), vec![]);
+        assert_eq!(factor(big(1039)), vec![(big(1039), 1)]);
+        assert_eq!(factor(big(39)), vec![(big(3), 1), (big(13), 1)]);
+        assert_eq!(
+            factor(big(720)),
+            vec![(big(2), 4), (big(3), 2), (big(5), 1)]
+        );
+    }
+
+    #[test]
+    fn test_02_pollard_rho() {
+        // dos primos de 32 bits: la division de prueba no los encuentra
+        let (p, q) = (big(4294967311), big(4294967357));
+        assert_eq!(factor(p * q), vec![(p, 1), (q, 1)]);
+        assert_eq!(
+            factor(p * p * big(12)),
+            vec![(big(2), 2), (big(3), 1), (p, 2)]
+        );
+    }
+
+    #[test]
+    fn test_03_primality() {
+        let primes: Vec<u64> = (0..200).filter(|n| is_small_prime(*n)).collect();
+        for n in 0..200 {
+            assert_eq!(is_probable_prime(big(n)), primes.contains(&n), "n = {}", n);
+        }
+        // numero de Carmichael 561 = 3 * 11 * 17 y un pseudoprimo fuerte en base 2
+        assert!(!is_probable_prime(big(561)));
+        assert!(!is_probable_prime(big(3215031751)));
+        assert!(is_probable_prime(big((1 << 61) - 1)));
+    }
+
+    #[test]
+    fn test_04_named_curve_orders() {
+        // el orden de P-256 es primo
+        let n = BigInteger::from_str_radix(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+            16,
+        )
+        .unwrap();
+        assert!(is_probable_prime(n));
+        assert_eq!(factor(n * big(4)), vec![(big(2), 2), (n, 1)]);
+        assert_eq!(gcd(n * big(6), BigInteger::from(-15)), big(3));
+    }
+
+    #[test]
+    fn test_05_divisors() {
+        assert_eq!(divisors(big(1)), vec![big(1)]);
+        assert_eq!(divisors(big(39)), vec![big(1), big(3), big(13), big(39)]);
+        let expected: Vec<BigInteger> = (1..=720).filter(|d| 720 % d == 0).map(big).collect();
+        assert_eq!(divisors(big(720)), expected);
+    }
+}
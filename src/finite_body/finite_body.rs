@@ -1,8 +1,9 @@
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Div, Neg, Rem, Sub};
 
 use num::{One, Zero};
 
-#[derive(Copy, Clone, Debug, Hash)]
+#[derive(Copy, Clone, Debug)]
 pub struct FiniteBody<T> {
     pub p: T,
     pub value: T,
@@ -22,9 +23,11 @@ where
         + One,
 {
     pub fn new(p: T, value: T) -> Self {
+        // una sola division: el resto tiene el signo de value
+        let value = value % p;
         FiniteBody {
             p,
-            value: (value % p + p) % p,
+            value: if value < T::zero() { value + p } else { value },
         }
     }
 
@@ -47,12 +50,7 @@ where
 
         // with fermat little theorem
         // for p prime
-        let mut acum = Self::new(self.p, T::one()); //= self.value.pow(self.p - 2);
-        let mut p_menos_2 = self.p - T::one() - T::one();
-        while p_menos_2 > T::zero() {
-            acum = acum * (*self);
-            p_menos_2 = p_menos_2 - T::one();
-        }
+        let acum = self.pow_mod(self.p - T::one() - T::one()); //= self.value.pow(self.p - 2);
 
         if acum.as_value() == T::zero() {
             None
@@ -61,11 +59,108 @@ where
         }
     }
 
+    /// Potencia con un exponente del mismo tipo que el modulo, por el metodo de "square and multiply".
+    /// Hace O(log(exponent)) multiplicaciones en lugar de `exponent` como el producto repetido.
+    pub fn pow_mod(&self, exponent: T) -> Self {
+        let two = T::one() + T::one();
+        let mut result = Self::new(self.p, T::one());
+        let mut base = *self;
+        let mut exponent = exponent;
+        while exponent > T::zero() {
+            let bit = exponent % two;
+            if bit == T::one() {
+                result = result * base;
+            }
+            base = base * base;
+            exponent = (exponent - bit) / two;
+        }
+        result
+    }
+
+    /// Simbolo de Legendre (value / p) por el criterio de Euler: 0 si value = 0, 1 si es un cuadrado
+    /// no nulo y -1 si no es un cuadrado (p primo impar).
+    pub fn legendre(&self) -> i8 {
+        if self.value == T::zero() {
+            return 0;
+        }
+        let two = T::one() + T::one();
+        match self.pow_mod((self.p - T::one()) / two).value == T::one() {
+            true => 1,
+            false => -1,
+        }
+    }
+
+    /// Una raiz cuadrada de self (la otra es su opuesto), o None si no es un cuadrado. Algoritmo de
+    /// Tonelli-Shanks, para p primo impar.
+    pub fn sqrt(&self) -> Option<Self> {
+        match self.legendre() {
+            0 => return Some(*self),
+            -1 => return None,
+            _ => {}
+        }
+
+        // p - 1 = q * 2^s con q impar
+        let two = T::one() + T::one();
+        let mut q = self.p - T::one();
+        let mut s = 0;
+        while q % two == T::zero() {
+            q = q / two;
+            s += 1;
+        }
+
+        // un no residuo cuadratico cualquiera
+        let mut z = Self::new(self.p, two);
+        while z.legendre() != -1 {
+            z = Self::new(self.p, z.value + T::one());
+        }
+
+        let mut m = s;
+        let mut c = z.pow_mod(q);
+        let mut t = self.pow_mod(q);
+        let mut root = self.pow_mod((q + T::one()) / two);
+        while t.value != T::one() {
+            // menor i con t^(2^i) = 1
+            let mut i = 0;
+            let mut t_power = t;
+            while t_power.value != T::one() {
+                t_power = t_power * t_power;
+                i += 1;
+            }
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b * b;
+            }
+            m = i;
+            c = b * b;
+            t = t * c;
+            root = root * b;
+        }
+        Some(root)
+    }
+
     pub fn as_value(&self) -> T {
         self.value
     }
 }
 
+impl<T: From<u8>> FiniteBody<T> {
+    /// 1 si `bit` es true y 0 si no, sin reducir ni multiplicar: sirve de `choice` para los
+    /// intercambios condicionales.
+    pub fn from_bit(p: T, bit: bool) -> Self {
+        FiniteBody {
+            p,
+            value: T::from(bit as u8),
+        }
+    }
+}
+
+impl<T: Hash> Hash for FiniteBody<T> {
+    // solo el valor, igual que PartialEq
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
 #[cfg(test)]
 mod test_finite_body {
     use super::*;
@@ -118,4 +213,42 @@ mod test_finite_body {
         assert!(FiniteBody::new(p, 0).inverse().is_none());
         assert!(FiniteBody::new(p, 7).inverse().is_none());
     }
+
+    #[test]
+    fn test_06_pow_mod() {
+        let p = 1021;
+        assert_eq!(FiniteBody::new(p, 3).pow_mod(0), 1);
+        assert_eq!(FiniteBody::new(p, 3).pow_mod(5), 243);
+        assert_eq!(FiniteBody::new(p, 379).pow_mod(p - 1), 1); // pequeño teorema de fermat
+    }
+
+    #[test]
+    fn test_07_legendre() {
+        let p = 43;
+        let squares: Vec<i32> = (1..p).map(|x| x * x % p).collect();
+        for value in 0..p {
+            let expected = match value {
+                0 => 0,
+                v if squares.contains(&v) => 1,
+                _ => -1,
+            };
+            assert_eq!(FiniteBody::new(p, value).legendre(), expected);
+        }
+    }
+
+    #[test]
+    fn test_08_sqrt() {
+        // 1021 = 1 (mod 4) y 1009 = 1 + 2^4 * 63 necesitan el caso general de Tonelli-Shanks
+        for p in [43, 223, 1009, 1021] {
+            for value in 0..p {
+                let element = FiniteBody::new(p, value);
+                match element.sqrt() {
+                    Some(root) => assert_eq!(root * root, element),
+                    None => assert_eq!(element.legendre(), -1),
+                }
+            }
+        }
+        assert!(FiniteBody::new(7, 3).sqrt().is_none());
+        assert_eq!(FiniteBody::new(7, 0).sqrt().unwrap(), 0);
+    }
 }
@@ -1,6 +1,8 @@
 pub mod add;
 pub mod div;
+#[allow(clippy::module_inception)]
 pub mod finite_body;
 pub mod mul;
 pub mod others;
+pub mod polynomial;
 pub mod sub;
@@ -41,6 +41,7 @@ where
     type Output = Self;
 
     /// Multiplicacion por un escalar positivo, deberia ser USIZE pero no pude hacerlo funcionar y me quedo ISIZE.
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn mul(self, other: isize) -> Self::Output {
         let mut result = Self::new(self.p, T::zero());
 
@@ -41,8 +41,19 @@ where
 {
     type Output = Self;
 
+    /// Potencia por "square and multiply", reduciendo modulo p en cada paso para no desbordar `T`.
     fn pow(self, rhs: usize) -> Self::Output {
-        Self::new(self.p, self.value.pow(rhs))
+        let mut result = Self::new(self.p, T::one());
+        let mut base = self;
+        let mut exponent = rhs;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        result
     }
 }
 
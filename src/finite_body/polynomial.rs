@@ -0,0 +1,505 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num::{traits::Pow, One, Zero};
+
+use crate::big_integer::BigInteger;
+
+use super::finite_body::FiniteBody;
+
+/// Polinomio con coeficientes en F_p. `coefficients[i]` es el coeficiente de x^i y el ultimo
+/// coeficiente nunca es cero, asi el polinomio nulo es el vector vacio. Guarda p para poder
+/// construir el cero y el uno sin coeficientes de referencia.
+#[derive(Debug, Clone)]
+pub struct Polynomial<X> {
+    p: X,
+    coefficients: Vec<FiniteBody<X>>,
+}
+
+impl<X> Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    /// c_0 + c_1 x + ... + c_n x^n, reduciendo cada coeficiente modulo p.
+    pub fn new(p: X, coefficients: &[X]) -> Self {
+        Self::from_coefficients(
+            p,
+            coefficients
+                .iter()
+                .map(|c| FiniteBody::new(p, *c))
+                .collect(),
+        )
+    }
+
+    pub fn from_coefficients(p: X, coefficients: Vec<FiniteBody<X>>) -> Self {
+        let mut polynomial = Polynomial { p, coefficients };
+        polynomial.normalize();
+        polynomial
+    }
+
+    pub fn zero(p: X) -> Self {
+        Polynomial {
+            p,
+            coefficients: vec![],
+        }
+    }
+
+    pub fn constant(c: FiniteBody<X>) -> Self {
+        Self::from_coefficients(c.p, vec![c])
+    }
+
+    pub fn one(p: X) -> Self {
+        Self::constant(FiniteBody::new(p, X::one()))
+    }
+
+    /// El polinomio x.
+    pub fn x(p: X) -> Self {
+        Self::new(p, &[X::zero(), X::one()])
+    }
+
+    pub fn p(&self) -> X {
+        self.p
+    }
+
+    pub fn coefficients(&self) -> &[FiniteBody<X>] {
+        &self.coefficients
+    }
+
+    /// Grado del polinomio, None para el polinomio nulo.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    pub fn leading_coefficient(&self) -> Option<FiniteBody<X>> {
+        self.coefficients.last().copied()
+    }
+
+    /// Valor en x por el metodo de Horner.
+    pub fn evaluate(&self, x: FiniteBody<X>) -> FiniteBody<X> {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(FiniteBody::new(self.p, X::zero()), |acc, c| acc * x + *c)
+    }
+
+    /// c * self.
+    pub fn scale(&self, c: FiniteBody<X>) -> Self {
+        Self::from_coefficients(self.p, self.coefficients.iter().map(|a| *a * c).collect())
+    }
+
+    /// El polinomio dividido por su coeficiente principal. El nulo queda igual.
+    pub fn monic(&self) -> Self {
+        match self.leading_coefficient() {
+            Some(leading) => self.scale(inverse(leading)),
+            None => self.clone(),
+        }
+    }
+
+    /// Cociente y resto de la division por `divisor`. Panics si el divisor es nulo.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor_degree = divisor.degree().expect("attempt to divide by zero");
+        // los modulos suelen ser monicos, asi se evita invertir en cada reduccion
+        let leading = divisor.coefficients[divisor_degree];
+        let leading_inverse = match leading.value.is_one() {
+            true => leading,
+            false => inverse(leading),
+        };
+        let mut remainder = self.coefficients.clone();
+        if remainder.len() <= divisor_degree {
+            return (Self::zero(self.p), self.clone());
+        }
+
+        let mut quotient =
+            vec![FiniteBody::new(self.p, X::zero()); remainder.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor_degree] * leading_inverse;
+            quotient[i] = factor;
+            for (j, c) in divisor.coefficients.iter().enumerate() {
+                let r = remainder[i + j].value;
+                remainder[i + j] = FiniteBody::new(self.p, r - factor.value * c.value);
+            }
+        }
+        remainder.truncate(divisor_degree);
+        (
+            Self::from_coefficients(self.p, quotient),
+            Self::from_coefficients(self.p, remainder),
+        )
+    }
+
+    /// Maximo comun divisor monico, por el algoritmo de Euclides.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let remainder = &a % &b;
+            a = b;
+            b = remainder;
+        }
+        a.monic()
+    }
+
+    /// Inverso de self modulo `modulus` con el algoritmo de Euclides extendido, o None si no son
+    /// coprimos.
+    pub fn inverse_mod(&self, modulus: &Self) -> Option<Self> {
+        let (mut old_r, mut r) = (self % modulus, modulus.clone());
+        let (mut old_s, mut s) = (Self::one(self.p), Self::zero(self.p));
+        while !r.is_zero() {
+            let (quotient, remainder) = old_r.div_rem(&r);
+            (old_r, r) = (r, remainder);
+            let next_s = &old_s - &(&quotient * &s);
+            (old_s, s) = (s, next_s);
+        }
+        // old_r es el gcd, tiene que ser una constante no nula
+        match old_r.degree() {
+            Some(0) => Some(&old_s.scale(inverse(old_r.coefficients[0])) % modulus),
+            _ => None,
+        }
+    }
+
+    /// self^exponent modulo `modulus`, por "square and multiply".
+    pub fn pow_mod<K: Into<BigInteger>>(&self, exponent: K, modulus: &Self) -> Self {
+        let exponent = exponent.into();
+        let base = self % modulus;
+        let mut result = &Self::one(self.p) % modulus;
+        for i in (0..exponent.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exponent.bit(i) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        result
+    }
+
+    /// self(inner) modulo `modulus`, por el metodo de Horner.
+    pub fn compose_mod(&self, inner: &Self, modulus: &Self) -> Self {
+        let inner = inner % modulus;
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Self::zero(self.p), |acc, c| {
+                &(&(&acc * &inner) + &Self::constant(*c)) % modulus
+            })
+    }
+
+    fn normalize(&mut self) {
+        while self.coefficients.last().is_some_and(|c| c.value.is_zero()) {
+            self.coefficients.pop();
+        }
+    }
+}
+
+impl<X> Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One
+        + Into<BigInteger>,
+{
+    /// Las raices distintas del polinomio en F_p, ordenadas, para p primo impar. gcd(x^p - x, f)
+    /// es el producto de los (x - r) y se separa con Cantor-Zassenhaus: para cada s, las raices r
+    /// con r + s un cuadrado no nulo son las de gcd((x + s)^((p - 1) / 2) - 1, f). Panics si el
+    /// polinomio es nulo.
+    pub fn roots(&self) -> Vec<FiniteBody<X>> {
+        assert!(
+            !self.is_zero(),
+            "the zero polynomial has every element as a root"
+        );
+        let x = Self::x(self.p);
+        let split = (&x.pow_mod(self.p, self) - &x).gcd(self);
+
+        let mut roots = vec![];
+        let mut pending = vec![split];
+        while let Some(factor) = pending.pop() {
+            match factor.degree() {
+                Some(0) | None => {}
+                // factor monico x - r
+                Some(1) => roots.push(-factor.coefficients[0]),
+                Some(degree) => {
+                    let (left, right) = self.equal_degree_split(&factor, degree);
+                    pending.push(left);
+                    pending.push(right);
+                }
+            }
+        }
+        roots.sort_by(|a, b| {
+            a.value
+                .partial_cmp(&b.value)
+                .expect("values are comparable")
+        });
+        roots
+    }
+
+    // Un factor propio de `factor` (producto de x - r distintos) y el cociente
+    fn equal_degree_split(&self, factor: &Self, degree: usize) -> (Self, Self) {
+        let two = X::one() + X::one();
+        let exponent = (self.p - X::one()) / two;
+        let mut shift = Self::x(self.p);
+        loop {
+            let power = &shift.pow_mod(exponent, factor) - &Self::one(self.p);
+            let candidate = power.gcd(factor);
+            if candidate.degree().is_some_and(|d| d > 0 && d < degree) {
+                let quotient = factor.div_rem(&candidate).0;
+                return (candidate, quotient);
+            }
+            shift = &shift + &Self::one(self.p);
+        }
+    }
+}
+
+impl<X: PartialEq> PartialEq for Polynomial<X> {
+    // coeficiente a coeficiente, como en `FiniteBody` solo se comparan los valores
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients.len() == other.coefficients.len()
+            && self
+                .coefficients
+                .iter()
+                .zip(&other.coefficients)
+                .all(|(a, b)| a.value == b.value)
+    }
+}
+
+// el coeficiente principal nunca es cero, asi que siempre es inversible (p primo)
+fn inverse<X>(c: FiniteBody<X>) -> FiniteBody<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    c.inverse().expect("the modulus is not prime")
+}
+
+impl<X> Add for &Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Polynomial<X>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let (long, short) = match self.coefficients.len() >= other.coefficients.len() {
+            true => (self, other),
+            false => (other, self),
+        };
+        let mut coefficients = long.coefficients.clone();
+        for (c, s) in coefficients.iter_mut().zip(&short.coefficients) {
+            *c = *c + *s;
+        }
+        Polynomial::from_coefficients(self.p, coefficients)
+    }
+}
+
+impl<X> Neg for &Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Polynomial<X>;
+
+    fn neg(self) -> Self::Output {
+        Polynomial::from_coefficients(self.p, self.coefficients.iter().map(|c| -*c).collect())
+    }
+}
+
+impl<X> Sub for &Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Polynomial<X>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + &(-other)
+    }
+}
+
+impl<X> Mul for &Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Polynomial<X>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero(self.p);
+        }
+        let zero = FiniteBody::new(self.p, X::zero());
+        let mut coefficients = vec![zero; self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                // se reduce una sola vez por termino, c + a b < p^2 + p
+                let c = coefficients[i + j].value;
+                coefficients[i + j] = FiniteBody::new(self.p, c + a.value * b.value);
+            }
+        }
+        Polynomial::from_coefficients(self.p, coefficients)
+    }
+}
+
+impl<X> Rem for &Polynomial<X>
+where
+    X: Add<X>
+        + Mul<Output = X>
+        + Div<Output = X>
+        + Sub<Output = X>
+        + Neg<Output = X>
+        + Rem<Output = X>
+        + Pow<usize, Output = X>
+        + PartialOrd
+        + Copy
+        + Zero
+        + One,
+{
+    type Output = Polynomial<X>;
+
+    fn rem(self, modulus: Self) -> Self::Output {
+        self.div_rem(modulus).1
+    }
+}
+
+#[cfg(test)]
+mod test_polynomial {
+    use super::*;
+
+    fn poly(coefficients: &[i64]) -> Polynomial<i64> {
+        Polynomial::new(13, coefficients)
+    }
+
+    #[test]
+    fn test_01_arithmetic() {
+        let a = poly(&[1, 2, 3]);
+        let b = poly(&[12, 11]);
+        assert_eq!(&a + &b, poly(&[0, 0, 3]));
+        assert_eq!(&a - &a, Polynomial::zero(13));
+        // (1 + 2x + 3x^2)(-1 - 2x) = -1 - 4x - 7x^2 - 6x^3
+        assert_eq!(&a * &b, poly(&[-1, -4, -7, -6]));
+        assert_eq!(poly(&[5, 0, 0, 0]).degree(), Some(0));
+        assert_eq!(Polynomial::<i64>::zero(13).degree(), None);
+        assert_eq!(a.evaluate(FiniteBody::new(13, 2)), FiniteBody::new(13, 17));
+    }
+
+    #[test]
+    fn test_02_division() {
+        let a = poly(&[3, 1, 4, 1, 5, 9, 2, 6]);
+        let b = poly(&[2, 7, 1, 8]);
+        let (quotient, remainder) = a.div_rem(&b);
+        assert_eq!(&(&quotient * &b) + &remainder, a);
+        assert!(remainder.degree() < b.degree());
+        assert_eq!(b.div_rem(&a), (Polynomial::zero(13), b.clone()));
+    }
+
+    #[test]
+    fn test_03_gcd_and_inverse() {
+        // (x - 1)(x - 2) y (x - 1)(x - 3)
+        let a = poly(&[2, -3, 1]);
+        let b = poly(&[3, -4, 1]);
+        assert_eq!(a.gcd(&b), poly(&[-1, 1]));
+        assert!(a.inverse_mod(&b).is_none());
+
+        let modulus = poly(&[2, 0, 1, 1]);
+        let c = poly(&[5, 7]);
+        let inverse = c.inverse_mod(&modulus).unwrap();
+        assert_eq!(&(&c * &inverse) % &modulus, Polynomial::one(13));
+    }
+
+    #[test]
+    fn test_04_pow_and_compose() {
+        // square and multiply contra 13 productos
+        let modulus = poly(&[1, 0, 1]);
+        let a = poly(&[3, 4]);
+        let mut expected = Polynomial::one(13);
+        for _ in 0..13 {
+            expected = &(&expected * &a) % &modulus;
+        }
+        assert_eq!(a.pow_mod(13, &modulus), expected);
+
+        // Frobenius: a(x)^p = a(x^p), con coeficientes en F_p
+        let modulus = poly(&[2, 0, 1, 1, 0, 1]);
+        let x_p = Polynomial::x(13).pow_mod(13, &modulus);
+        assert_eq!(a.pow_mod(13, &modulus), a.compose_mod(&x_p, &modulus));
+    }
+
+    #[test]
+    fn test_05_roots() {
+        let roots = |p: i64, coefficients: &[i64]| -> Vec<i64> {
+            let polynomial = Polynomial::new(p, coefficients);
+            polynomial.roots().iter().map(|r| r.value).collect()
+        };
+        // (x - 1)(x - 2)(x - 3)(x - 5), con la raiz 3 doble
+        let mut product = poly(&[1]);
+        for root in [1, 2, 3, 3, 5] {
+            product = &product * &poly(&[-root, 1]);
+        }
+        assert_eq!(product, poly(&[1, 5, 11, 9, 12, 1]));
+        assert_eq!(roots(13, &[1, 5, 11, 9, 12, 1]), vec![1, 2, 3, 5]);
+
+        // x^3 - x sobre F_1019 y x^2 + 1, que no tiene raices si p = 3 mod 4
+        assert_eq!(roots(1019, &[0, -1, 0, 1]), vec![0, 1, 1018]);
+        assert_eq!(roots(1019, &[1, 0, 1]), Vec::<i64>::new());
+        assert_eq!(roots(1021, &[1, 0, 1]).len(), 2);
+        assert_eq!(roots(13, &[4]), Vec::<i64>::new());
+    }
+}
@@ -1,3 +1,6 @@
 pub mod agreement;
+pub mod big_integer;
 pub mod elliptic_curve;
+pub mod factorization;
 pub mod finite_body;
+pub mod sha256;